@@ -0,0 +1,25 @@
+use cidr_routing_table::Ipv4Cidr;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::net::Ipv4Addr;
+
+fn bench_cidr_max(c: &mut Criterion) {
+    let cidrs: Vec<Ipv4Cidr> = (0..=32u8)
+        .cycle()
+        .take(1024)
+        .enumerate()
+        .map(|(idx, len)| {
+            Ipv4Cidr::containing(Ipv4Addr::from(idx as u32 * 2_654_435_761), len).unwrap()
+        })
+        .collect();
+
+    c.bench_function("Ipv4Cidr::max", |b| {
+        b.iter(|| {
+            for cidr in black_box(&cidrs) {
+                black_box(cidr.max());
+            }
+        });
+    });
+}
+
+criterion_group!(benches, bench_cidr_max);
+criterion_main!(benches);
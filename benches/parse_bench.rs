@@ -0,0 +1,30 @@
+use cidr_routing_table::{load_cidrs, par_load_cidrs, Ipv4Cidr};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::iter::repeat_with;
+
+fn bench_load_cidrs(c: &mut Criterion) {
+    let sizes = [10000, 1000000];
+    let mut rng = rand::thread_rng();
+    let mut group = c.benchmark_group("LoadCidrs");
+
+    for size in sizes {
+        let owned: Vec<String> = repeat_with(|| Ipv4Cidr::random(&mut rng).to_string())
+            .take(size)
+            .collect();
+        let lines: Vec<&str> = owned.iter().map(String::as_str).collect();
+
+        group.sample_size(10);
+        group.bench_function(BenchmarkId::new("Serial", size), |b| {
+            b.iter(|| load_cidrs(&lines));
+        });
+
+        group.bench_function(BenchmarkId::new("Parallel", size), |b| {
+            b.iter(|| par_load_cidrs(&lines));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_load_cidrs);
+criterion_main!(benches);
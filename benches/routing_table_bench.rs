@@ -1,37 +1,62 @@
 use cidr_routing_table::{
-    get_cidr_mask, HashRoutingTable, Ipv4Cidr, ListRoutingTable, RoutingTable, TrieRoutingTable,
+    HashRoutingTable, IntervalRoutingTable, Ipv4Cidr, RoutingTable, TrieRoutingTable,
 };
 use criterion::{
     criterion_group, criterion_main, AxisScale, BenchmarkId, Criterion, PlotConfiguration,
 };
 use mimalloc::MiMalloc;
 use rand::prelude::*;
-use std::{iter::repeat_with, net::Ipv4Addr};
+use std::{
+    hash::{BuildHasherDefault, Hasher},
+    iter::repeat_with,
+    net::Ipv4Addr,
+};
 
 #[global_allocator]
 static GLOBAL: MiMalloc = MiMalloc;
 
-fn generate_cidr(bits: u32, len: u8) -> Ipv4Cidr {
-    let mask = get_cidr_mask(len).expect("Len should be smaller than equal to 32");
-    let new_bits = bits & mask;
-    let addr = Ipv4Addr::from(new_bits);
+#[derive(Default)]
+struct FxHasher {
+    hash: u64,
+}
+
+impl Hasher for FxHasher {
+    fn finish(&self) -> u64 {
+        // Network keys have their low bits cleared, so move the well-mixed high bits down.
+        self.hash.rotate_left(26)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write_u64(u64::from(byte));
+        }
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write_u64(u64::from(i));
+    }
 
-    Ipv4Cidr::new(addr, len).expect("Input is correct")
+    fn write_u64(&mut self, i: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ i).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
 }
 
+type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
 fn bench_routing_table(c: &mut Criterion) {
     let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
     // let sizes = [10, 100, 1000, 10000, 100000, 1000000];
-    let sizes = [10000];
+    let sizes = [10000, 1000000];
     let mut rng = rand::thread_rng();
     let mut group = c.benchmark_group("CidrManager");
     group.plot_config(plot_config);
 
     for size in sizes {
-        let cidrs = repeat_with(|| generate_cidr(rng.gen(), rng.gen_range(0..=32)));
+        let cidrs = repeat_with(|| Ipv4Cidr::random(&mut rng));
         let mut trie_routing_table = TrieRoutingTable::new();
         let mut hash_routing_table = HashRoutingTable::new();
-        let mut list_routing_table = ListRoutingTable::new();
+        let mut fx_hash_routing_table = HashRoutingTable::with_hasher(FxBuildHasher::default());
+        let mut inserted = Vec::with_capacity(size);
 
         for cidr in cidrs {
             if hash_routing_table.size() >= size {
@@ -40,9 +65,12 @@ fn bench_routing_table(c: &mut Criterion) {
 
             trie_routing_table.add_cidr(cidr);
             hash_routing_table.add_cidr(cidr);
-            list_routing_table.add_cidr(cidr);
+            fx_hash_routing_table.add_cidr(cidr);
+            inserted.push(cidr);
         }
 
+        let interval_routing_table = IntervalRoutingTable::from_cidrs(&inserted);
+
         println!("Table size: {}", hash_routing_table.size());
 
         group.sample_size(1000);
@@ -58,6 +86,18 @@ fn bench_routing_table(c: &mut Criterion) {
             );
         });
 
+        group.bench_function(BenchmarkId::new("FxHashCidrManager", size), |b| {
+            let mut addresses = repeat_with(|| Ipv4Addr::from(rng.gen::<u32>()));
+
+            b.iter_batched(
+                || addresses.next().unwrap(),
+                |addr| {
+                    fx_hash_routing_table.find_exact_cidr(addr);
+                },
+                criterion::BatchSize::SmallInput,
+            );
+        });
+
         group.bench_function(BenchmarkId::new("TrieCidrManager", size), |b| {
             let mut addresses = repeat_with(|| Ipv4Addr::from(rng.gen::<u32>()));
 
@@ -69,17 +109,17 @@ fn bench_routing_table(c: &mut Criterion) {
                 criterion::BatchSize::SmallInput,
             );
         });
-        // group.bench_function(BenchmarkId::new("ListCidrManager", size), |b| {
-        //     let mut addresses = repeat_with(|| Ipv4Addr::from(rng.gen::<u32>()));
-
-        //     b.iter_batched(
-        //         || addresses.next().unwrap(),
-        //         |addr| {
-        //             list_routing_table.find_exact_cidr(addr);
-        //         },
-        //         criterion::BatchSize::SmallInput,
-        //     );
-        // });
+        group.bench_function(BenchmarkId::new("IntervalCidrManager", size), |b| {
+            let mut addresses = repeat_with(|| Ipv4Addr::from(rng.gen::<u32>()));
+
+            b.iter_batched(
+                || addresses.next().unwrap(),
+                |addr| {
+                    interval_routing_table.find_exact_cidr(addr);
+                },
+                criterion::BatchSize::SmallInput,
+            );
+        });
 
         println!("Finished tests with size {}", size);
     }
@@ -87,5 +127,94 @@ fn bench_routing_table(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_routing_table);
+fn bench_trie_load(c: &mut Criterion) {
+    let sizes = [10000, 1000000];
+    let mut rng = rand::thread_rng();
+    let mut group = c.benchmark_group("TrieLoad");
+
+    for size in sizes {
+        let cidrs: Vec<Ipv4Cidr> = repeat_with(|| Ipv4Cidr::random(&mut rng))
+            .take(size)
+            .collect();
+
+        group.sample_size(10);
+        group.bench_function(BenchmarkId::new("Unsorted", size), |b| {
+            b.iter_batched(
+                || cidrs.clone(),
+                |cidrs| {
+                    let mut trie_routing_table = TrieRoutingTable::new();
+                    for cidr in cidrs {
+                        trie_routing_table.add_cidr(cidr);
+                    }
+                    trie_routing_table
+                },
+                criterion::BatchSize::LargeInput,
+            );
+        });
+
+        group.bench_function(BenchmarkId::new("Sorted", size), |b| {
+            b.iter_batched(
+                || cidrs.clone(),
+                |mut cidrs| TrieRoutingTable::load_sorted(&mut cidrs),
+                criterion::BatchSize::LargeInput,
+            );
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_short_prefix_heavy(c: &mut Criterion) {
+    let size = 100000;
+    let mut rng = rand::thread_rng();
+    let mut group = c.benchmark_group("ShortPrefixHeavy");
+    let mut hash_routing_table = HashRoutingTable::new();
+    let mut fx_hash_routing_table = HashRoutingTable::with_hasher(FxBuildHasher::default());
+
+    // 95% of the routes are /8-/16, the rest anywhere in /17-/32.
+    while hash_routing_table.size() < size {
+        let len = if rng.gen_ratio(95, 100) {
+            rng.gen_range(8..=16)
+        } else {
+            rng.gen_range(17..=32)
+        };
+        let cidr = Ipv4Cidr::containing(Ipv4Addr::from(rng.gen::<u32>()), len).unwrap();
+
+        hash_routing_table.add_cidr(cidr);
+        fx_hash_routing_table.add_cidr(cidr);
+    }
+
+    group.bench_function(BenchmarkId::new("HashCidrManager", size), |b| {
+        let mut addresses = repeat_with(|| Ipv4Addr::from(rng.gen::<u32>()));
+
+        b.iter_batched(
+            || addresses.next().unwrap(),
+            |addr| {
+                hash_routing_table.find_exact_cidr(addr);
+            },
+            criterion::BatchSize::SmallInput,
+        );
+    });
+
+    group.bench_function(BenchmarkId::new("FxHashCidrManager", size), |b| {
+        let mut addresses = repeat_with(|| Ipv4Addr::from(rng.gen::<u32>()));
+
+        b.iter_batched(
+            || addresses.next().unwrap(),
+            |addr| {
+                fx_hash_routing_table.find_exact_cidr(addr);
+            },
+            criterion::BatchSize::SmallInput,
+        );
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_routing_table,
+    bench_trie_load,
+    bench_short_prefix_heavy
+);
 criterion_main!(benches);
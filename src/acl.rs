@@ -0,0 +1,64 @@
+use crate::{Ipv4Cidr, RoutingTable};
+use std::net::Ipv4Addr;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum AclDecision {
+    Allow(Ipv4Cidr),
+    Deny(Ipv4Cidr),
+    NoMatch,
+}
+
+pub fn resolve_acl(
+    allow: &dyn RoutingTable,
+    deny: &dyn RoutingTable,
+    addr: Ipv4Addr,
+) -> AclDecision {
+    match (allow.find_exact_cidr(addr), deny.find_exact_cidr(addr)) {
+        (Some(allowed), Some(denied)) if allowed.prefix_len() > denied.prefix_len() => {
+            AclDecision::Allow(allowed)
+        }
+        (_, Some(denied)) => AclDecision::Deny(denied),
+        (Some(allowed), None) => AclDecision::Allow(allowed),
+        (None, None) => AclDecision::NoMatch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve_acl, AclDecision};
+    use crate::{HashRoutingTable, Ipv4Cidr, RoutingTable, TrieRoutingTable};
+    use std::{net::Ipv4Addr, str::FromStr};
+
+    #[test]
+    fn test_resolve_acl() {
+        let mut allow = TrieRoutingTable::new();
+        let mut deny = HashRoutingTable::new();
+        let corporate = Ipv4Cidr::from_str("10.0.0.0/8").unwrap();
+        let quarantine = Ipv4Cidr::from_str("10.66.0.0/16").unwrap();
+        let jump_host = Ipv4Cidr::from_str("10.66.0.10/32").unwrap();
+        let shared = Ipv4Cidr::from_str("172.16.0.0/12").unwrap();
+
+        allow.add_cidr(corporate);
+        allow.add_cidr(jump_host);
+        allow.add_cidr(shared);
+        deny.add_cidr(quarantine);
+        deny.add_cidr(shared);
+
+        let test_cases = [
+            (Ipv4Addr::new(10, 1, 2, 3), AclDecision::Allow(corporate)),
+            (Ipv4Addr::new(10, 66, 1, 1), AclDecision::Deny(quarantine)),
+            (Ipv4Addr::new(10, 66, 0, 10), AclDecision::Allow(jump_host)),
+            (Ipv4Addr::new(172, 16, 5, 5), AclDecision::Deny(shared)),
+            (Ipv4Addr::new(192, 168, 0, 1), AclDecision::NoMatch),
+        ];
+
+        for (addr, expected) in test_cases {
+            let decision = resolve_acl(&allow, &deny, addr);
+
+            assert_eq!(
+                decision, expected,
+                "we resolve {addr} against acl and expect decision to be {expected:?}"
+            );
+        }
+    }
+}
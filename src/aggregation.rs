@@ -0,0 +1,473 @@
+use crate::{utils::merge_ranges, Ipv4Cidr, RoutingTable};
+use std::{net::Ipv4Addr, ops::Range};
+
+// Keeps every aggregate alongside the original cidrs merged into it, so a summarized set
+// can be expanded again.
+pub struct AggregationMap {
+    aggregates: Vec<(Ipv4Cidr, Vec<Ipv4Cidr>)>,
+}
+
+impl AggregationMap {
+    pub fn from_cidrs(cidrs: &[Ipv4Cidr]) -> Self {
+        let mut originals = cidrs.to_vec();
+        originals.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+        originals.dedup();
+
+        let mut aggregates: Vec<(Ipv4Cidr, Vec<Ipv4Cidr>)> = aggregate(&originals)
+            .into_iter()
+            .map(|cidr| (cidr, Vec::new()))
+            .collect();
+
+        // Aggregates are disjoint and sorted, so each original lands in the last one
+        // starting at or before it.
+        for cidr in originals {
+            let idx =
+                aggregates.partition_point(|(aggregate, _)| aggregate.min() <= cidr.min()) - 1;
+            aggregates[idx].1.push(cidr);
+        }
+
+        Self { aggregates }
+    }
+
+    pub fn aggregates(&self) -> Vec<Ipv4Cidr> {
+        self.aggregates
+            .iter()
+            .map(|(aggregate, _)| *aggregate)
+            .collect()
+    }
+
+    // Empty when `aggregate` is not one of `aggregates()`.
+    pub fn disaggregate(&self, aggregate: Ipv4Cidr) -> Vec<Ipv4Cidr> {
+        self.aggregates
+            .binary_search_by_key(&aggregate.min(), |(cidr, _)| cidr.min())
+            .ok()
+            .filter(|&idx| self.aggregates[idx].0 == aggregate)
+            .map(|idx| self.aggregates[idx].1.clone())
+            .unwrap_or_default()
+    }
+}
+
+pub fn aggregate(cidrs: &[Ipv4Cidr]) -> Vec<Ipv4Cidr> {
+    merge_ranges(cidrs.iter().map(|cidr| (cidr.min(), cidr.max())).collect())
+        .into_iter()
+        .flat_map(|(start, end)| range_to_cidrs(start, end))
+        .collect()
+}
+
+// Splits an inclusive range into the fewest aligned blocks, in ascending order.
+pub fn range_to_cidrs(start: Ipv4Addr, end: Ipv4Addr) -> Vec<Ipv4Cidr> {
+    let mut cidrs = Vec::new();
+    let mut current = u64::from(u32::from(start));
+    let end = u64::from(u32::from(end));
+
+    while current <= end {
+        let mut size = if current == 0 {
+            1 << 32
+        } else {
+            current & current.wrapping_neg()
+        };
+        while current + size - 1 > end {
+            size >>= 1;
+        }
+
+        let len = 32 - size.trailing_zeros() as u8;
+        cidrs
+            .push(Ipv4Cidr::from_bits(current as u32, len).expect("Block sizes never exceed 2^32"));
+        current += size;
+    }
+
+    cidrs
+}
+
+// True when both cover exactly the same addresses, however differently they are split.
+pub fn verify_aggregation(original: &dyn RoutingTable, aggregated: &[Ipv4Cidr]) -> bool {
+    let expected = merge_ranges(
+        original
+            .iter()
+            .map(|cidr| (cidr.min(), cidr.max()))
+            .collect(),
+    );
+    let actual = merge_ranges(
+        aggregated
+            .iter()
+            .map(|cidr| (cidr.min(), cidr.max()))
+            .collect(),
+    );
+
+    expected == actual
+}
+
+// Empty unless `a` lies entirely before `b` with at least one address between them.
+pub fn gap_between(a: Ipv4Cidr, b: Ipv4Cidr) -> Vec<Ipv4Cidr> {
+    let (a_max, b_min) = (u32::from(a.max()), u32::from(b.min()));
+    if a_max >= b_min {
+        return Vec::new();
+    }
+
+    range_to_cidrs(Ipv4Addr::from(a_max + 1), Ipv4Addr::from(b_min - 1))
+}
+
+pub fn enclosing_cidr(cidrs: &[Ipv4Cidr]) -> Option<Ipv4Cidr> {
+    let (first, rest) = cidrs.split_first()?;
+
+    Some(
+        rest.iter()
+            .fold(*first, |acc, cidr| acc.common_supernet(cidr)),
+    )
+}
+
+pub fn cover_addresses(addrs: &[Ipv4Addr], max_prefixes: usize) -> Vec<Ipv4Cidr> {
+    let mut hosts: Vec<Ipv4Cidr> = addrs.iter().map(|&addr| Ipv4Cidr::new_host(addr)).collect();
+    hosts.sort_unstable_by_key(|cidr| cidr.min());
+    hosts.dedup();
+
+    let mut cidrs = merge_siblings(hosts);
+
+    while cidrs.len() > max_prefixes.max(1) {
+        let (run, supernet) = cidrs
+            .windows(2)
+            .map(|pair| {
+                let supernet = pair[0].common_supernet(&pair[1]);
+                (covered_run(&cidrs, &supernet), supernet)
+            })
+            .min_by_key(|(run, supernet)| {
+                let covered: u64 = cidrs[run.clone()].iter().map(|c| c.num_addresses()).sum();
+                supernet.num_addresses() - covered
+            })
+            .expect("There should always be at least two cidrs to merge");
+
+        cidrs.splice(run, [supernet]);
+        cidrs = merge_siblings(cidrs);
+    }
+
+    cidrs
+}
+
+// Expects disjoint cidrs sorted by network address.
+fn merge_siblings(cidrs: Vec<Ipv4Cidr>) -> Vec<Ipv4Cidr> {
+    let mut merged: Vec<Ipv4Cidr> = Vec::with_capacity(cidrs.len());
+
+    for cidr in cidrs {
+        let mut current = cidr;
+        while let Some(parent) = merged.last().and_then(|last| last.merge_with(&current)) {
+            merged.pop();
+            current = parent;
+        }
+
+        merged.push(current);
+    }
+
+    merged
+}
+
+// Disjoint sorted cidrs inside `supernet` always form a contiguous run.
+fn covered_run(cidrs: &[Ipv4Cidr], supernet: &Ipv4Cidr) -> Range<usize> {
+    let start = cidrs.partition_point(|cidr| cidr.min() < supernet.min());
+    let end = cidrs.partition_point(|cidr| cidr.min() <= supernet.max());
+
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        aggregate, cover_addresses, enclosing_cidr, gap_between, range_to_cidrs,
+        verify_aggregation, AggregationMap,
+    };
+    use crate::{Ipv4Cidr, RoutingTable, TrieRoutingTable};
+    use std::{net::Ipv4Addr, str::FromStr};
+
+    fn parse_all(cidrs: &[&str]) -> Vec<Ipv4Cidr> {
+        cidrs
+            .iter()
+            .map(|s| Ipv4Cidr::from_str(s).expect("Cidr is correct"))
+            .collect()
+    }
+
+    #[test]
+    fn test_cover_clustered_addresses() {
+        let mut addrs: Vec<Ipv4Addr> = (0..8).map(|i| Ipv4Addr::new(10, 0, 0, i)).collect();
+        addrs.push(Ipv4Addr::new(192, 168, 1, 1));
+        addrs.push(Ipv4Addr::new(10, 0, 0, 3));
+
+        let test_cases = [
+            (10, parse_all(&["10.0.0.0/29", "192.168.1.1/32"])),
+            (2, parse_all(&["10.0.0.0/29", "192.168.1.1/32"])),
+            (1, parse_all(&["0.0.0.0/0"])),
+        ];
+
+        for (max_prefixes, expected) in test_cases {
+            let result = cover_addresses(&addrs, max_prefixes);
+
+            assert_eq!(
+                result, expected,
+                "we expect clustered addresses within {max_prefixes} prefixes to be {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cover_scattered_addresses() {
+        let addrs = [
+            Ipv4Addr::new(10, 200, 0, 2),
+            Ipv4Addr::new(10, 0, 0, 1),
+            Ipv4Addr::new(10, 0, 0, 6),
+            Ipv4Addr::new(10, 200, 0, 1),
+        ];
+
+        let test_cases = [
+            (
+                4,
+                parse_all(&[
+                    "10.0.0.1/32",
+                    "10.0.0.6/32",
+                    "10.200.0.1/32",
+                    "10.200.0.2/32",
+                ]),
+            ),
+            (
+                3,
+                parse_all(&["10.0.0.1/32", "10.0.0.6/32", "10.200.0.0/30"]),
+            ),
+            (2, parse_all(&["10.0.0.0/29", "10.200.0.0/30"])),
+            (1, parse_all(&["10.0.0.0/8"])),
+            (0, parse_all(&["10.0.0.0/8"])),
+        ];
+
+        for (max_prefixes, expected) in test_cases {
+            let result = cover_addresses(&addrs, max_prefixes);
+
+            assert_eq!(
+                result, expected,
+                "we expect scattered addresses within {max_prefixes} prefixes to be {expected:?}"
+            );
+        }
+
+        assert_eq!(cover_addresses(&[], 3), vec![]);
+    }
+
+    #[test]
+    fn test_range_to_cidrs() {
+        let test_cases = [
+            ("10.0.0.0", "10.0.0.255", vec!["10.0.0.0/24"]),
+            (
+                "10.0.0.1",
+                "10.0.0.6",
+                vec!["10.0.0.1/32", "10.0.0.2/31", "10.0.0.4/31", "10.0.0.6/32"],
+            ),
+            ("0.0.0.0", "255.255.255.255", vec!["0.0.0.0/0"]),
+            (
+                "255.255.255.255",
+                "255.255.255.255",
+                vec!["255.255.255.255/32"],
+            ),
+            ("10.0.1.0", "10.0.0.0", vec![]),
+        ];
+
+        for (start, end, expected) in test_cases {
+            let result = range_to_cidrs(start.parse().unwrap(), end.parse().unwrap());
+
+            assert_eq!(
+                result,
+                parse_all(&expected),
+                "we expect range {start}-{end} to split into {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let cidrs = parse_all(&[
+            "10.0.0.128/25",
+            "10.0.0.0/25",
+            "10.0.0.64/26",
+            "10.0.1.0/24",
+            "10.0.3.0/24",
+            "192.168.0.1/32",
+        ]);
+
+        assert_eq!(
+            aggregate(&cidrs),
+            parse_all(&["10.0.0.0/23", "10.0.3.0/24", "192.168.0.1/32"])
+        );
+        assert_eq!(aggregate(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_verify_aggregation() {
+        let mut routing_table = TrieRoutingTable::new();
+        for cidr in parse_all(&[
+            "10.0.0.0/25",
+            "10.0.0.128/25",
+            "10.0.0.64/26",
+            "10.0.3.0/24",
+        ]) {
+            routing_table.add_cidr(cidr);
+        }
+
+        let test_cases = [
+            (aggregate(&routing_table.iter().collect::<Vec<_>>()), true),
+            (parse_all(&["10.0.0.0/24", "10.0.3.0/24"]), true),
+            (
+                parse_all(&["10.0.3.0/24", "10.0.0.0/25", "10.0.0.128/25"]),
+                true,
+            ),
+            (parse_all(&["10.0.0.0/22"]), false),
+            (parse_all(&["10.0.0.0/24"]), false),
+            (
+                parse_all(&["10.0.0.0/24", "10.0.3.0/24", "10.0.3.0/32"]),
+                true,
+            ),
+            (vec![], false),
+        ];
+
+        for (aggregated, expected) in test_cases {
+            assert_eq!(
+                verify_aggregation(&routing_table, &aggregated),
+                expected,
+                "we expect {aggregated:?} to verify as {expected}"
+            );
+        }
+
+        assert!(verify_aggregation(&TrieRoutingTable::new(), &[]));
+    }
+
+    #[test]
+    fn test_gap_between() {
+        let test_cases = [
+            ("10.0.0.0/24", "10.0.1.0/24", vec![]),
+            (
+                "10.0.0.0/24",
+                "10.0.4.0/24",
+                vec!["10.0.1.0/24", "10.0.2.0/23"],
+            ),
+            ("10.0.0.0/25", "10.0.0.132/30", vec!["10.0.0.128/30"]),
+            ("10.0.0.0/8", "10.1.0.0/16", vec![]),
+            ("10.0.0.0/24", "10.0.0.0/24", vec![]),
+            ("10.0.4.0/24", "10.0.0.0/24", vec![]),
+            (
+                "0.0.0.0/1",
+                "255.255.255.255/32",
+                vec![
+                    "128.0.0.0/2",
+                    "192.0.0.0/3",
+                    "224.0.0.0/4",
+                    "240.0.0.0/5",
+                    "248.0.0.0/6",
+                    "252.0.0.0/7",
+                    "254.0.0.0/8",
+                    "255.0.0.0/9",
+                    "255.128.0.0/10",
+                    "255.192.0.0/11",
+                    "255.224.0.0/12",
+                    "255.240.0.0/13",
+                    "255.248.0.0/14",
+                    "255.252.0.0/15",
+                    "255.254.0.0/16",
+                    "255.255.0.0/17",
+                    "255.255.128.0/18",
+                    "255.255.192.0/19",
+                    "255.255.224.0/20",
+                    "255.255.240.0/21",
+                    "255.255.248.0/22",
+                    "255.255.252.0/23",
+                    "255.255.254.0/24",
+                    "255.255.255.0/25",
+                    "255.255.255.128/26",
+                    "255.255.255.192/27",
+                    "255.255.255.224/28",
+                    "255.255.255.240/29",
+                    "255.255.255.248/30",
+                    "255.255.255.252/31",
+                    "255.255.255.254/32",
+                ],
+            ),
+        ];
+
+        for (a, b, expected) in test_cases {
+            let result = gap_between(
+                Ipv4Cidr::from_str(a).unwrap(),
+                Ipv4Cidr::from_str(b).unwrap(),
+            );
+
+            assert_eq!(
+                result,
+                parse_all(&expected),
+                "we expect the gap between {a} and {b} to be {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_aggregation_map() {
+        let cidrs = parse_all(&[
+            "10.0.0.128/25",
+            "192.168.0.1/32",
+            "10.0.0.0/25",
+            "10.0.0.64/26",
+            "10.0.0.0/25",
+        ]);
+        let map = AggregationMap::from_cidrs(&cidrs);
+
+        assert_eq!(
+            map.aggregates(),
+            parse_all(&["10.0.0.0/24", "192.168.0.1/32"])
+        );
+        assert_eq!(
+            map.disaggregate(Ipv4Cidr::from_str("10.0.0.0/24").unwrap()),
+            parse_all(&["10.0.0.0/25", "10.0.0.64/26", "10.0.0.128/25"])
+        );
+        assert_eq!(
+            map.disaggregate(Ipv4Cidr::from_str("192.168.0.1/32").unwrap()),
+            parse_all(&["192.168.0.1/32"])
+        );
+        assert_eq!(
+            map.disaggregate(Ipv4Cidr::from_str("10.0.0.0/25").unwrap()),
+            vec![]
+        );
+        assert_eq!(
+            map.disaggregate(Ipv4Cidr::from_str("172.16.0.0/12").unwrap()),
+            vec![]
+        );
+        assert_eq!(AggregationMap::from_cidrs(&[]).aggregates(), vec![]);
+
+        let halves = parse_all(&["10.1.0.0/25", "10.1.0.128/25"]);
+        let map = AggregationMap::from_cidrs(&halves);
+        let parent = Ipv4Cidr::from_str("10.1.0.0/24").unwrap();
+
+        assert_eq!(map.aggregates(), vec![parent]);
+        assert_eq!(map.disaggregate(parent), halves);
+    }
+
+    #[test]
+    fn test_enclosing_cidr() {
+        let test_cases = [
+            (vec!["10.1.2.0/24"], Some("10.1.2.0/24")),
+            (vec!["10.1.2.0/25", "10.1.2.128/25"], Some("10.1.2.0/24")),
+            (
+                vec!["10.1.2.7/32", "10.1.3.0/24", "10.1.0.0/23"],
+                Some("10.1.0.0/22"),
+            ),
+            (vec!["10.1.2.0/24", "10.0.0.0/8"], Some("10.0.0.0/8")),
+            (
+                vec!["10.0.0.1/32", "172.16.0.0/12", "192.168.1.0/24"],
+                Some("0.0.0.0/0"),
+            ),
+            (
+                vec!["192.168.1.0/24", "192.168.200.0/24"],
+                Some("192.168.0.0/16"),
+            ),
+            (vec![], None),
+        ];
+
+        for (cidrs, expected) in test_cases {
+            let expected = expected.map(|s| Ipv4Cidr::from_str(s).unwrap());
+            let result = enclosing_cidr(&parse_all(&cidrs));
+
+            assert_eq!(
+                result, expected,
+                "we expect {cidrs:?} to be enclosed by {expected:?}"
+            );
+        }
+    }
+}
@@ -1,36 +1,77 @@
-use std::{net::Ipv4Addr, str::FromStr};
+use std::{cmp::Ordering, fmt, net::Ipv4Addr, str::FromStr};
 
 use crate::{
-    errors::NetworkParseError,
-    utils::{get_cidr_mask, MAX_LENGTH},
+    errors::{MergeError, NetworkParseError},
+    family::{AddrFamily, Ipv4},
+    prefix_len::PrefixLen,
+    utils::{cut_addr, divergence_prefix_len, get_cidr_mask, MASKS, MAX_LENGTH},
 };
 
-#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[derive(PartialEq, Eq, Clone, Copy, Hash)]
 pub struct Ipv4Cidr {
     addr: Ipv4Addr,
     len: u8,
 }
 
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum CidrRelation {
+    Equal,
+    Supernet,
+    Subnet,
+    Disjoint,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum CidrStyle {
+    Prefix,
+    Netmask,
+    Wildcard,
+}
+
 impl Ipv4Cidr {
     pub fn new(addr: Ipv4Addr, len: u8) -> Result<Self, NetworkParseError> {
-        let mask = get_cidr_mask(len)?;
+        let prefix = PrefixLen::new_unchecked(Ipv4::validate_len(len)?);
+        let mask = prefix.mask();
         let bits = u32::from(addr);
 
         if (bits & mask) != bits {
             Err(NetworkParseError::NetworkLengthError)
         } else {
-            Ok(Self { addr, len })
+            Ok(Self {
+                addr,
+                len: prefix.get(),
+            })
         }
     }
 
+    pub fn from_octets(octets: [u8; 4], len: u8) -> Result<Self, NetworkParseError> {
+        Self::new(Ipv4Addr::from(octets), len)
+    }
+
+    // Unlike `from_bits`, host bits are rejected just as in `new`.
+    pub fn from_u32(bits: u32, len: u8) -> Result<Self, NetworkParseError> {
+        Self::new(Ipv4Addr::from(bits), len)
+    }
+
     pub fn from_bits(bits: u32, len: u8) -> Result<Self, NetworkParseError> {
-        if len > MAX_LENGTH {
-            Err(NetworkParseError::NetworkLengthError)
-        } else {
-            let addr = Ipv4Addr::from(bits);
+        let len = Ipv4::validate_len(len)?;
 
-            Ok(Self {addr, len })
-        }
+        Ok(Self {
+            addr: Ipv4Addr::from(bits),
+            len,
+        })
+    }
+
+    pub fn from_bits_truncate(bits: u32, len: u8) -> Result<Self, NetworkParseError> {
+        let mask = get_cidr_mask(len)?;
+
+        Self::from_bits(bits & mask, len)
+    }
+
+    pub fn containing(addr: Ipv4Addr, len: u8) -> Result<Self, NetworkParseError> {
+        let network = cut_addr(addr, len)?;
+
+        Ok(Self { addr: network, len })
     }
 
     pub fn new_host(addr: Ipv4Addr) -> Self {
@@ -44,28 +85,300 @@ impl Ipv4Cidr {
         self.len
     }
 
+    pub fn prefix(&self) -> PrefixLen {
+        PrefixLen::new_unchecked(self.len)
+    }
+
+    pub fn with_prefix_len(&self, len: u8) -> Result<Ipv4Cidr, NetworkParseError> {
+        Self::containing(self.addr, len)
+    }
+
     pub fn min(&self) -> Ipv4Addr {
         self.addr
     }
 
     pub fn max(&self) -> Ipv4Addr {
         let bits = u32::from(self.addr);
-        let mask = get_cidr_mask(self.len)
-            .unwrap_or_else(|_| panic!("{} should always be lower than or equal to 32", self.len));
-        let reversed_mask = u32::MAX ^ mask;
+        let reversed_mask = u32::MAX ^ MASKS[self.len as usize];
 
         let max_bits = bits | reversed_mask;
         Ipv4Addr::from(max_bits)
     }
 
+    pub fn netmask(&self) -> Ipv4Addr {
+        Ipv4Addr::from(MASKS[self.len as usize])
+    }
+
+    pub fn to_string_with(&self, style: CidrStyle) -> String {
+        match style {
+            CidrStyle::Prefix => self.to_string(),
+            CidrStyle::Netmask => format!("{} {}", self.addr, self.netmask()),
+            CidrStyle::Wildcard => {
+                format!(
+                    "{} {}",
+                    self.addr,
+                    Ipv4Addr::from(!MASKS[self.len as usize])
+                )
+            }
+        }
+    }
+
     pub fn contains(&self, addr: Ipv4Addr) -> bool {
         let lower = self.min();
         let upper = self.max();
 
         lower <= addr && addr <= upper
     }
+
+    pub fn contains_cidr(&self, other: &Ipv4Cidr) -> bool {
+        self.len <= other.len && self.contains(other.addr)
+    }
+
+    // Two prefixes are either nested or disjoint, so one containment check in each
+    // direction is enough.
+    pub fn relation(&self, other: &Ipv4Cidr) -> CidrRelation {
+        if self.contains_cidr(other) {
+            if self.len == other.len {
+                CidrRelation::Equal
+            } else {
+                CidrRelation::Supernet
+            }
+        } else if other.contains_cidr(self) {
+            CidrRelation::Subnet
+        } else {
+            CidrRelation::Disjoint
+        }
+    }
+
+    pub fn is_host(&self) -> bool {
+        self.len == MAX_LENGTH
+    }
+
+    pub fn host_address(&self) -> Option<Ipv4Addr> {
+        self.is_host().then_some(self.addr)
+    }
+
+    pub fn num_addresses(&self) -> u64 {
+        1 << (MAX_LENGTH - self.len)
+    }
+
+    pub fn host_offset(&self, addr: Ipv4Addr) -> Option<u32> {
+        self.contains(addr)
+            .then(|| u32::from(addr) - u32::from(self.min()))
+    }
+
+    pub fn peer_address(&self, addr: Ipv4Addr) -> Option<Ipv4Addr> {
+        (self.len == 31 && self.contains(addr)).then(|| Ipv4Addr::from(u32::from(addr) ^ 1))
+    }
+
+    pub fn network(&self) -> Ipv4Addr {
+        self.min()
+    }
+
+    /// `/31` point-to-point links (RFC 3021) have no broadcast address, while a `/32`
+    /// host route is its own network and broadcast address.
+    pub fn broadcast(&self) -> Option<Ipv4Addr> {
+        match self.len {
+            31 => None,
+            _ => Some(self.max()),
+        }
+    }
+
+    /// Usable host addresses: everything except the network and broadcast addresses,
+    /// except for `/31` (both addresses) and `/32` (the single address).
+    pub fn hosts(&self) -> impl DoubleEndedIterator<Item = Ipv4Addr> {
+        let (first, last) = self.host_range();
+
+        (first..=last).map(Ipv4Addr::from)
+    }
+
+    pub fn hosts_rev(&self) -> impl Iterator<Item = Ipv4Addr> {
+        self.hosts().rev()
+    }
+
+    pub fn addresses(&self) -> impl DoubleEndedIterator<Item = Ipv4Addr> {
+        (u32::from(self.min())..=u32::from(self.max())).map(Ipv4Addr::from)
+    }
+
+    pub fn addresses_rev(&self) -> impl Iterator<Item = Ipv4Addr> {
+        self.addresses().rev()
+    }
+
+    pub fn usable_hosts(&self) -> u64 {
+        let (first, last) = self.host_range();
+
+        u64::from(last - first) + 1
+    }
+
+    pub fn is_valid_host(&self, addr: Ipv4Addr) -> bool {
+        let (first, last) = self.host_range();
+        let bits = u32::from(addr);
+
+        first <= bits && bits <= last
+    }
+
+    pub fn subnets(&self, len: u8) -> impl DoubleEndedIterator<Item = Ipv4Cidr> {
+        let start = u64::from(u32::from(self.addr));
+        let (count, step) = if self.len <= len && len <= MAX_LENGTH {
+            (1u64 << (len - self.len), 1u64 << (MAX_LENGTH - len))
+        } else {
+            (0, 1)
+        };
+
+        (0..count).map(move |idx| Self {
+            addr: Ipv4Addr::from((start + idx * step) as u32),
+            len,
+        })
+    }
+
+    pub fn to_slash24s(&self) -> Vec<Ipv4Cidr> {
+        if self.len <= 24 {
+            self.subnets(24).collect()
+        } else {
+            vec![Self::containing(self.addr, 24).expect("24 is a valid length")]
+        }
+    }
+
+    pub fn index_within(&self, parent: &Ipv4Cidr) -> Option<u32> {
+        if self.len <= parent.len || !parent.contains(self.addr) {
+            return None;
+        }
+
+        let offset = u32::from(self.addr) - u32::from(parent.addr);
+        Some(offset >> (MAX_LENGTH - self.len))
+    }
+
+    pub fn merge_with(&self, other: &Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.try_merge_with(other).ok()
+    }
+
+    pub fn try_merge_with(&self, other: &Ipv4Cidr) -> Result<Ipv4Cidr, MergeError> {
+        if self.len != other.len {
+            return Err(MergeError::DifferentLength);
+        }
+        if self.len == 0 || self == other {
+            return Err(MergeError::NotSiblings);
+        }
+
+        let parent = self.common_supernet(other);
+        if parent.len + 1 == self.len {
+            return Ok(parent);
+        }
+
+        let (lower, upper) = if self.addr < other.addr {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if u64::from(u32::from(lower.max())) + 1 == u64::from(u32::from(upper.min())) {
+            Err(MergeError::MisalignedParent)
+        } else {
+            Err(MergeError::NotSiblings)
+        }
+    }
+
+    // The other half of the parent block; the default route has none.
+    pub fn sibling(&self) -> Option<Ipv4Cidr> {
+        if self.len == 0 {
+            return None;
+        }
+
+        Some(Self {
+            addr: Ipv4Addr::from(u32::from(self.addr) ^ (1 << (MAX_LENGTH - self.len))),
+            len: self.len,
+        })
+    }
+
+    pub fn is_sibling_of(&self, other: &Ipv4Cidr) -> bool {
+        self.sibling() == Some(*other)
+    }
+
+    pub fn common_supernet(&self, other: &Ipv4Cidr) -> Ipv4Cidr {
+        let len = divergence_prefix_len(self.addr, other.addr)
+            .min(self.len)
+            .min(other.len);
+        let mask = get_cidr_mask(len).expect("Len should always be lower than or equal to 32");
+
+        Self {
+            addr: Ipv4Addr::from(u32::from(self.addr) & mask),
+            len,
+        }
+    }
+
+    pub fn exclude(&self, other: &Ipv4Cidr) -> Vec<Ipv4Cidr> {
+        if !self.contains(other.min()) && !other.contains(self.min()) {
+            return vec![*self];
+        }
+
+        let mut remaining = Vec::new();
+        let mut current = *self;
+
+        while current.len < other.len {
+            let (lower, upper) = current.halves();
+
+            if lower.contains(other.min()) {
+                remaining.push(upper);
+                current = lower;
+            } else {
+                remaining.push(lower);
+                current = upper;
+            }
+        }
+
+        remaining.sort_unstable_by_key(|cidr| cidr.addr);
+        remaining
+    }
+
+    pub fn complement(&self, scope: Ipv4Cidr) -> Vec<Ipv4Cidr> {
+        scope.exclude(self)
+    }
+
+    pub fn split_around(&self, addr: Ipv4Addr) -> Vec<Ipv4Cidr> {
+        self.exclude(&Ipv4Cidr::new_host(addr))
+    }
+
+    fn host_range(&self) -> (u32, u32) {
+        let first = u32::from(self.min());
+        let last = u32::from(self.max());
+
+        if self.len >= 31 {
+            (first, last)
+        } else {
+            (first + 1, last - 1)
+        }
+    }
+
+    fn halves(&self) -> (Ipv4Cidr, Ipv4Cidr) {
+        let len = self.len + 1;
+        let upper_bits = u32::from(self.addr) | (1 << (MAX_LENGTH - len));
+
+        (
+            Self {
+                addr: self.addr,
+                len,
+            },
+            Self {
+                addr: Ipv4Addr::from(upper_bits),
+                len,
+            },
+        )
+    }
 }
 
+impl fmt::Display for Ipv4Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.len)
+    }
+}
+
+impl fmt::Debug for Ipv4Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ipv4Cidr({self})")
+    }
+}
+
+// The length goes through `u8::from_str`, so `/024` and `/+8` are accepted here; use
+// `Ipv4Cidr::from_str_strict` when only the canonical spelling is acceptable.
 impl FromStr for Ipv4Cidr {
     type Err = NetworkParseError;
 
@@ -85,11 +398,124 @@ impl FromStr for Ipv4Cidr {
     }
 }
 
+impl TryFrom<&str> for Ipv4Cidr {
+    type Error = NetworkParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
+
+impl TryFrom<String> for Ipv4Cidr {
+    type Error = NetworkParseError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_str(&s)
+    }
+}
+
+impl Ipv4Cidr {
+    // Same as `from_str`, but the length must be plain ASCII digits without a sign or a
+    // leading zero, so every accepted string matches what `Display` prints.
+    pub fn from_str_strict(s: &str) -> Result<Self, NetworkParseError> {
+        let (_, len_part) = s.split_once('/').ok_or(NetworkParseError::CidrParseError)?;
+
+        let is_canonical = !len_part.is_empty()
+            && len_part.bytes().all(|byte| byte.is_ascii_digit())
+            && (len_part == "0" || !len_part.starts_with('0'));
+        if !is_canonical {
+            return Err(NetworkParseError::CidrParseError);
+        }
+
+        Self::from_str(s)
+    }
+
+    pub fn from_str_lenient(s: &str) -> Result<Self, NetworkParseError> {
+        let (addr_part, len_part) = s.split_once('/').ok_or(NetworkParseError::CidrParseError)?;
+
+        let octets = addr_part.split('.').count();
+        let padded = if octets < 4 {
+            format!("{addr_part}{}", ".0".repeat(4 - octets))
+        } else {
+            addr_part.to_string()
+        };
+
+        Self::from_str(&format!("{padded}/{len_part}"))
+    }
+
+    pub fn parse_lenient(s: &str) -> Vec<Ipv4Cidr> {
+        s.split(|c: char| c.is_whitespace() || c == ',')
+            .filter_map(|token| Self::from_str(token).ok())
+            .collect()
+    }
+}
+
+// Largest block first; blocks of the same size are ordered by network address.
+pub fn cmp_by_size(a: &Ipv4Cidr, b: &Ipv4Cidr) -> Ordering {
+    a.len.cmp(&b.len).then(a.addr.cmp(&b.addr))
+}
+
+// Accepts `a.b.c.d/len`, the lenient shorthand (`10.1/16`), a bare integer address
+// (`167772160/8`) and a dotted netmask in place of the length (`10.0.0.0/255.0.0.0`).
+fn parse_any_notation(s: &str) -> Result<Ipv4Cidr, NetworkParseError> {
+    let (addr_part, len_part) = s
+        .trim()
+        .split_once('/')
+        .ok_or(NetworkParseError::CidrParseError)?;
+
+    let len = if len_part.contains('.') {
+        let mask =
+            u32::from(Ipv4Addr::from_str(len_part).map_err(NetworkParseError::AddrParseError)?);
+        if mask.leading_ones() + mask.trailing_zeros() != 32 {
+            return Err(NetworkParseError::CidrParseError);
+        }
+
+        mask.leading_ones() as u8
+    } else {
+        len_part
+            .parse::<u8>()
+            .map_err(NetworkParseError::ParseIntError)?
+    };
+
+    if addr_part.contains('.') {
+        Ipv4Cidr::from_str_lenient(&format!("{addr_part}/{len}"))
+    } else {
+        let bits = addr_part
+            .parse::<u32>()
+            .map_err(NetworkParseError::ParseIntError)?;
+
+        Ipv4Cidr::from_u32(bits, len)
+    }
+}
+
+pub fn cidr_str_eq(a: &str, b: &str) -> bool {
+    match (parse_any_notation(a), parse_any_notation(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+// Error indices count every comma-separated segment, empty ones included, so they point at
+// the offending position in the input.
+pub fn parse_cidr_list(s: &str) -> Result<Vec<Ipv4Cidr>, (usize, NetworkParseError)> {
+    s.split(',')
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, part)| !part.is_empty())
+        .map(|(idx, part)| Ipv4Cidr::from_str(part).map_err(|err| (idx, err)))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::errors::NetworkParseError;
+    use crate::{
+        errors::{MergeError, NetworkParseError},
+        prefix_len::PrefixLen,
+    };
 
-    use super::{Ipv4Cidr, MAX_LENGTH};
+    use super::{
+        cidr_str_eq, cmp_by_size, parse_cidr_list, CidrRelation, CidrStyle, Ipv4Cidr, MAX_LENGTH,
+    };
     use std::{net::Ipv4Addr, str::FromStr};
 
     #[test]
@@ -141,6 +567,97 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_containing() {
+        let test_cases = [
+            (Ipv4Addr::new(192, 168, 1, 130), 24, "192.168.1.0/24"),
+            (Ipv4Addr::new(192, 168, 1, 130), 25, "192.168.1.128/25"),
+            (Ipv4Addr::new(192, 168, 1, 130), 16, "192.168.0.0/16"),
+            (Ipv4Addr::new(192, 168, 1, 130), 32, "192.168.1.130/32"),
+            (Ipv4Addr::new(192, 168, 1, 130), 0, "0.0.0.0/0"),
+        ];
+
+        for (addr, len, expected) in test_cases {
+            let expected = Ipv4Cidr::from_str(expected).unwrap();
+
+            assert_eq!(
+                Ipv4Cidr::containing(addr, len),
+                Ok(expected),
+                "we expect /{len} network containing {addr} to be {expected}"
+            );
+        }
+
+        assert_eq!(
+            Ipv4Cidr::containing(Ipv4Addr::new(192, 168, 1, 130), 33),
+            Err(NetworkParseError::NetworkLengthError)
+        );
+    }
+
+    #[test]
+    fn test_from_octets_and_u32() {
+        let test_cases = [
+            ([0, 0, 0, 0], 8),
+            ([0, 0, 0, 0], 32),
+            ([192, 168, 0, 0], 16),
+            ([192, 168, 0, 0], 13),
+            ([192, 168, 200, 4], 30),
+            ([192, 168, 200, 8], 30),
+            ([169, 254, 0, 0], 16),
+            ([127, 0, 0, 0], 8),
+            ([100, 64, 0, 0], 10),
+        ];
+
+        for (octets, len) in test_cases {
+            let expected = Ipv4Cidr::new(Ipv4Addr::from(octets), len);
+
+            assert!(expected.is_ok());
+            assert_eq!(Ipv4Cidr::from_octets(octets, len), expected);
+            assert_eq!(
+                Ipv4Cidr::from_u32(u32::from_be_bytes(octets), len),
+                expected
+            );
+        }
+
+        assert_eq!(
+            Ipv4Cidr::from_octets([192, 168, 1, 1], 24),
+            Err(NetworkParseError::NetworkLengthError)
+        );
+        assert_eq!(
+            Ipv4Cidr::from_u32(0xC0A8_0101, 24),
+            Err(NetworkParseError::NetworkLengthError)
+        );
+        assert_eq!(
+            Ipv4Cidr::from_octets([10, 0, 0, 0], 33),
+            Err(NetworkParseError::NetworkLengthError)
+        );
+    }
+
+    #[test]
+    fn test_from_bits_truncate() {
+        let test_cases = [
+            (0xC0A8_0182, 24, "192.168.1.0/24"),
+            (0xC0A8_0100, 24, "192.168.1.0/24"),
+            (0xC0A8_0182, 25, "192.168.1.128/25"),
+            (0xC0A8_0182, 32, "192.168.1.130/32"),
+            (0xFFFF_FFFF, 0, "0.0.0.0/0"),
+        ];
+
+        for (bits, len, expected) in test_cases {
+            let expected = Ipv4Cidr::from_str(expected).unwrap();
+
+            assert_eq!(
+                Ipv4Cidr::from_bits_truncate(bits, len),
+                Ok(expected),
+                "we expect {bits:#x}/{len} truncated to be {expected}"
+            );
+        }
+
+        assert_eq!(
+            Ipv4Cidr::from_bits_truncate(0xC0A8_0182, 33),
+            Err(NetworkParseError::NetworkLengthError)
+        );
+    }
+
     #[test]
     fn test_create_host_cidr() {
         let test_cases = [
@@ -190,6 +707,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_prefix() {
+        for cidr_str in ["0.0.0.0/0", "10.0.0.0/8", "192.168.1.0/24", "127.0.0.1/32"] {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+
+            assert_eq!(cidr.prefix().get(), cidr.prefix_len());
+            assert_eq!(PrefixLen::new(cidr.prefix_len()), Some(cidr.prefix()));
+        }
+    }
+
+    #[test]
+    fn test_with_prefix_len() {
+        let test_cases = [
+            ("192.168.1.0/24", 16, "192.168.0.0/16"),
+            ("192.168.1.0/24", 0, "0.0.0.0/0"),
+            ("192.168.1.0/24", 24, "192.168.1.0/24"),
+            ("192.168.1.0/24", 26, "192.168.1.0/26"),
+            ("192.168.1.128/25", 32, "192.168.1.128/32"),
+        ];
+
+        for (cidr_str, len, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let expected = Ipv4Cidr::from_str(expected).unwrap();
+
+            assert_eq!(
+                cidr.with_prefix_len(len),
+                Ok(expected),
+                "we expect {cidr_str} with length {len} to be {expected}"
+            );
+        }
+
+        let cidr = Ipv4Cidr::from_str("192.168.1.0/24").unwrap();
+        assert_eq!(
+            cidr.with_prefix_len(33),
+            Err(NetworkParseError::NetworkLengthError)
+        );
+    }
+
+    #[test]
+    fn test_netmask() {
+        let test_cases = [
+            ("0.0.0.0/0", Ipv4Addr::new(0, 0, 0, 0)),
+            ("10.0.0.0/8", Ipv4Addr::new(255, 0, 0, 0)),
+            ("172.16.0.0/12", Ipv4Addr::new(255, 240, 0, 0)),
+            ("192.168.1.0/24", Ipv4Addr::new(255, 255, 255, 0)),
+            ("192.168.1.1/32", Ipv4Addr::new(255, 255, 255, 255)),
+        ];
+
+        for (cidr_str, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+
+            assert_eq!(
+                cidr.netmask(),
+                expected,
+                "we expect netmask of {cidr_str} to be {expected}"
+            );
+        }
+    }
+
     #[test]
     fn test_min() {
         let test_cases = [
@@ -343,25 +919,361 @@ mod tests {
     }
 
     #[test]
-    fn test_contains_addr() {
+    fn test_parse_strict_str() {
         let test_cases = [
-            ("192.168.0.0/16", Ipv4Addr::new(192, 168, 0, 0)),
-            ("192.168.0.0/16", Ipv4Addr::new(192, 168, 0, 123)),
-            ("192.168.0.0/16", Ipv4Addr::new(192, 168, 0, 255)),
-            ("192.168.0.0/16", Ipv4Addr::new(192, 168, 255, 0)),
-            ("192.168.0.0/16", Ipv4Addr::new(192, 168, 255, 123)),
-            ("192.168.0.0/16", Ipv4Addr::new(192, 168, 255, 255)),
-            ("192.168.0.0/13", Ipv4Addr::new(192, 169, 0, 0)),
-            ("192.168.200.4/30", Ipv4Addr::new(192, 168, 200, 4)),
-            ("192.168.200.4/30", Ipv4Addr::new(192, 168, 200, 5)),
-            ("192.168.200.4/30", Ipv4Addr::new(192, 168, 200, 6)),
-            ("192.168.200.4/30", Ipv4Addr::new(192, 168, 200, 7)),
-            ("192.168.200.8/30", Ipv4Addr::new(192, 168, 200, 8)),
-            ("192.168.200.8/30", Ipv4Addr::new(192, 168, 200, 9)),
-            ("192.168.200.8/30", Ipv4Addr::new(192, 168, 200, 10)),
-            ("192.168.200.8/30", Ipv4Addr::new(192, 168, 200, 11)),
-            ("169.254.0.0/16", Ipv4Addr::new(169, 254, 0, 0)),
-            ("127.0.0.0/8", Ipv4Addr::new(127, 0, 0, 0)),
+            ("192.168.0.0/16", Ok(("192.168.0.0", 16))),
+            ("0.0.0.0/0", Ok(("0.0.0.0", 0))),
+            ("10.0.0.1/32", Ok(("10.0.0.1", 32))),
+            ("192.168.0.0/024", Err(NetworkParseError::CidrParseError)),
+            ("192.168.0.0/+8", Err(NetworkParseError::CidrParseError)),
+            ("10.0.0.0/08", Err(NetworkParseError::CidrParseError)),
+            ("0.0.0.0/00", Err(NetworkParseError::CidrParseError)),
+            ("10.0.0.0/-8", Err(NetworkParseError::CidrParseError)),
+            ("10.0.0.0/ 8", Err(NetworkParseError::CidrParseError)),
+            ("10.0.0.0/", Err(NetworkParseError::CidrParseError)),
+            ("10.0.0.0", Err(NetworkParseError::CidrParseError)),
+            ("10.0.0.0/33", Err(NetworkParseError::NetworkLengthError)),
+            ("10.0.0.1/8", Err(NetworkParseError::NetworkLengthError)),
+        ];
+
+        for (cidr_str, expected) in test_cases {
+            let expected =
+                expected.map(|(addr, len)| Ipv4Cidr::new(addr.parse().unwrap(), len).unwrap());
+
+            assert_eq!(
+                Ipv4Cidr::from_str_strict(cidr_str),
+                expected,
+                "we expect strict parsing of {cidr_str} to be {expected:?}"
+            );
+        }
+
+        assert_eq!(
+            Ipv4Cidr::from_str("192.168.0.0/024"),
+            Ipv4Cidr::from_str("192.168.0.0/24")
+        );
+        assert_eq!(
+            Ipv4Cidr::from_str("192.168.0.0/+8"),
+            Err(NetworkParseError::NetworkLengthError)
+        );
+    }
+
+    #[test]
+    fn test_try_from_str() {
+        let test_cases = [
+            "10.0.0.0/8",
+            "192.168.0.1/32",
+            "0.0.0.0/0",
+            "192.168.0.0/12",
+            "invalid/12",
+            "wrong",
+            "169.254.0.0/hello",
+        ];
+
+        for cidr_str in test_cases {
+            let expected = Ipv4Cidr::from_str(cidr_str);
+
+            assert_eq!(Ipv4Cidr::try_from(cidr_str), expected);
+            assert_eq!(Ipv4Cidr::try_from(cidr_str.to_string()), expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_lenient_shorthand() {
+        let test_cases = [
+            ("10/8", Ipv4Addr::new(10, 0, 0, 0), 8),
+            ("192.168/16", Ipv4Addr::new(192, 168, 0, 0), 16),
+            ("172.16.5/24", Ipv4Addr::new(172, 16, 5, 0), 24),
+            ("192.168.0.0/16", Ipv4Addr::new(192, 168, 0, 0), 16),
+            ("0/0", Ipv4Addr::new(0, 0, 0, 0), 0),
+        ];
+
+        for (cidr_str, addr, len) in test_cases {
+            assert_eq!(
+                Ipv4Cidr::from_str_lenient(cidr_str),
+                Ok(Ipv4Cidr { addr, len }),
+                "we expect {cidr_str} to be leniently parsed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_lenient_invalid_shorthand() {
+        let test_cases = [
+            (
+                "256/8",
+                Err(NetworkParseError::AddrParseError(
+                    "256.0.0.0".parse::<Ipv4Addr>().err().unwrap(),
+                )),
+            ),
+            (
+                "10..1/8",
+                Err(NetworkParseError::AddrParseError(
+                    "10..1.0".parse::<Ipv4Addr>().err().unwrap(),
+                )),
+            ),
+            ("10", Err(NetworkParseError::CidrParseError)),
+            ("10/33", Err(NetworkParseError::NetworkLengthError)),
+            ("10.1/8", Err(NetworkParseError::NetworkLengthError)),
+        ];
+
+        for (cidr_str, expected) in test_cases {
+            assert_eq!(
+                Ipv4Cidr::from_str_lenient(cidr_str),
+                expected,
+                "we expect {cidr_str} to be invalid"
+            );
+        }
+
+        assert_eq!(
+            Ipv4Cidr::from_str("10/8"),
+            Err(NetworkParseError::AddrParseError(
+                "10".parse::<Ipv4Addr>().err().unwrap(),
+            )),
+            "we expect strict parsing to reject shorthand"
+        );
+    }
+
+    #[test]
+    fn test_parse_lenient_many() {
+        let input = "10.0.0.0/8, 192.168.1.1\n172.16.0.0/12,,garbage\t192.168.0.0/33 \
+                     10.1.0.0/8 0.0.0.0/0\r\n";
+
+        assert_eq!(
+            Ipv4Cidr::parse_lenient(input),
+            vec![
+                Ipv4Cidr::from_str("10.0.0.0/8").unwrap(),
+                Ipv4Cidr::from_str("172.16.0.0/12").unwrap(),
+                Ipv4Cidr::from_str("0.0.0.0/0").unwrap(),
+            ]
+        );
+        assert_eq!(Ipv4Cidr::parse_lenient(""), vec![]);
+        assert_eq!(Ipv4Cidr::parse_lenient(" , \n"), vec![]);
+    }
+
+    #[test]
+    fn test_cmp_by_size() {
+        let mut cidrs: Vec<Ipv4Cidr> = [
+            "10.1.2.0/24",
+            "192.168.0.0/16",
+            "10.0.0.1/32",
+            "10.0.0.0/8",
+            "10.1.0.0/16",
+            "0.0.0.0/0",
+            "10.0.0.0/24",
+        ]
+        .iter()
+        .map(|s| Ipv4Cidr::from_str(s).unwrap())
+        .collect();
+        cidrs.sort_by(cmp_by_size);
+
+        let sorted: Vec<String> = cidrs.iter().map(|cidr| cidr.to_string()).collect();
+        assert_eq!(
+            sorted,
+            [
+                "0.0.0.0/0",
+                "10.0.0.0/8",
+                "10.1.0.0/16",
+                "192.168.0.0/16",
+                "10.0.0.0/24",
+                "10.1.2.0/24",
+                "10.0.0.1/32",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cidr_str_eq() {
+        let test_cases = [
+            ("192.168.0.0/16", "192.168.0.0/16", true),
+            ("192.168.0.0/16", "3232235520/16", true),
+            ("192.168.0.0/16", "192.168.0.0/255.255.0.0", true),
+            ("192.168/16", "3232235520/255.255.0.0", true),
+            (" 10.0.0.0/8", "167772160/8 ", true),
+            ("0.0.0.0/0", "0/0.0.0.0", true),
+            ("10.0.0.1/32", "167772161/255.255.255.255", true),
+            ("192.168.0.0/16", "192.168.0.0/17", false),
+            ("192.168.0.0/16", "3232235520/255.255.128.0", false),
+            ("192.168.0.0/16", "192.168.0.0/255.0.255.0", false),
+            ("192.168.0.0/16", "3232235521/16", false),
+            ("192.168.0.0/16", "4294967296/16", false),
+            ("192.168.0.0/16", "garbage", false),
+            ("garbage", "garbage", false),
+        ];
+
+        for (a, b, expected) in test_cases {
+            assert_eq!(
+                cidr_str_eq(a, b),
+                expected,
+                "we expect {a:?} and {b:?} to compare as {expected}"
+            );
+            assert_eq!(cidr_str_eq(b, a), expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_cidr_list() {
+        let expected = vec![
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(127, 0, 0, 1)),
+        ];
+
+        assert_eq!(
+            parse_cidr_list("10.0.0.0/8,192.168.0.0/16,127.0.0.1/32"),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            parse_cidr_list(" 10.0.0.0/8 , ,192.168.0.0/16,\t127.0.0.1/32, "),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_cidr_list() {
+        assert_eq!(parse_cidr_list(""), Ok(vec![]));
+        assert_eq!(parse_cidr_list(" , ,"), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_invalid_cidr_list() {
+        assert_eq!(
+            parse_cidr_list("10.0.0.0/8, 192.168.0.0/8, wrong"),
+            Err((1, NetworkParseError::NetworkLengthError))
+        );
+        assert_eq!(
+            parse_cidr_list("10.0.0.0/8,,wrong"),
+            Err((2, NetworkParseError::CidrParseError))
+        );
+        assert_eq!(
+            parse_cidr_list(" , 10.0.0.0/8, ,bad"),
+            Err((3, NetworkParseError::CidrParseError))
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let test_cases = ["0.0.0.0/0", "192.168.0.0/16", "127.0.0.1/32"];
+
+        for cidr_str in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            assert_eq!(cidr.to_string(), cidr_str);
+        }
+    }
+
+    #[test]
+    fn test_to_string_with() {
+        let test_cases = [
+            ("192.168.0.0/24", CidrStyle::Prefix, "192.168.0.0/24"),
+            (
+                "192.168.0.0/24",
+                CidrStyle::Netmask,
+                "192.168.0.0 255.255.255.0",
+            ),
+            (
+                "192.168.0.0/24",
+                CidrStyle::Wildcard,
+                "192.168.0.0 0.0.0.255",
+            ),
+            ("0.0.0.0/0", CidrStyle::Netmask, "0.0.0.0 0.0.0.0"),
+            ("0.0.0.0/0", CidrStyle::Wildcard, "0.0.0.0 255.255.255.255"),
+            ("10.0.0.1/32", CidrStyle::Wildcard, "10.0.0.1 0.0.0.0"),
+        ];
+
+        for (cidr_str, style, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+
+            assert_eq!(
+                cidr.to_string_with(style),
+                expected,
+                "we expect {cidr_str} in {style:?} style to be {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_debug() {
+        let cidr = Ipv4Cidr::from_str("192.168.0.0/16").unwrap();
+
+        assert_eq!(format!("{cidr:?}"), "Ipv4Cidr(192.168.0.0/16)");
+        assert_eq!(
+            format!(
+                "{:?}",
+                Some(Ipv4Cidr::new_host(Ipv4Addr::new(127, 0, 0, 1)))
+            ),
+            "Some(Ipv4Cidr(127.0.0.1/32))"
+        );
+    }
+
+    #[test]
+    fn test_contains_cidr() {
+        let test_cases = [
+            ("10.0.0.0/8", "10.0.0.0/8", true),
+            ("10.0.0.0/8", "10.1.0.0/16", true),
+            ("10.0.0.0/8", "10.255.255.255/32", true),
+            ("0.0.0.0/0", "192.168.0.0/16", true),
+            ("10.1.0.0/16", "10.0.0.0/8", false),
+            ("10.0.0.0/8", "11.0.0.0/16", false),
+            ("10.0.0.0/8", "0.0.0.0/0", false),
+        ];
+
+        for (cidr_str, other_str, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let other = Ipv4Cidr::from_str(other_str).unwrap();
+
+            assert_eq!(
+                cidr.contains_cidr(&other),
+                expected,
+                "we expect {cidr_str} containing {other_str} to be {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_relation() {
+        let test_cases = [
+            ("10.0.0.0/8", "10.0.0.0/8", CidrRelation::Equal),
+            ("0.0.0.0/0", "0.0.0.0/0", CidrRelation::Equal),
+            ("10.0.0.0/8", "10.1.0.0/16", CidrRelation::Supernet),
+            ("0.0.0.0/0", "255.255.255.255/32", CidrRelation::Supernet),
+            ("10.1.0.0/16", "10.0.0.0/8", CidrRelation::Subnet),
+            ("10.0.0.0/8", "10.0.0.0/9", CidrRelation::Supernet),
+            ("10.128.0.0/9", "10.0.0.0/9", CidrRelation::Disjoint),
+            ("10.0.0.0/8", "11.0.0.0/16", CidrRelation::Disjoint),
+            ("192.168.1.1/32", "192.168.1.2/32", CidrRelation::Disjoint),
+        ];
+
+        for (cidr_str, other_str, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let other = Ipv4Cidr::from_str(other_str).unwrap();
+
+            assert_eq!(
+                cidr.relation(&other),
+                expected,
+                "we expect relation of {cidr_str} to {other_str} to be {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_contains_addr() {
+        let test_cases = [
+            ("192.168.0.0/16", Ipv4Addr::new(192, 168, 0, 0)),
+            ("192.168.0.0/16", Ipv4Addr::new(192, 168, 0, 123)),
+            ("192.168.0.0/16", Ipv4Addr::new(192, 168, 0, 255)),
+            ("192.168.0.0/16", Ipv4Addr::new(192, 168, 255, 0)),
+            ("192.168.0.0/16", Ipv4Addr::new(192, 168, 255, 123)),
+            ("192.168.0.0/16", Ipv4Addr::new(192, 168, 255, 255)),
+            ("192.168.0.0/13", Ipv4Addr::new(192, 169, 0, 0)),
+            ("192.168.200.4/30", Ipv4Addr::new(192, 168, 200, 4)),
+            ("192.168.200.4/30", Ipv4Addr::new(192, 168, 200, 5)),
+            ("192.168.200.4/30", Ipv4Addr::new(192, 168, 200, 6)),
+            ("192.168.200.4/30", Ipv4Addr::new(192, 168, 200, 7)),
+            ("192.168.200.8/30", Ipv4Addr::new(192, 168, 200, 8)),
+            ("192.168.200.8/30", Ipv4Addr::new(192, 168, 200, 9)),
+            ("192.168.200.8/30", Ipv4Addr::new(192, 168, 200, 10)),
+            ("192.168.200.8/30", Ipv4Addr::new(192, 168, 200, 11)),
+            ("169.254.0.0/16", Ipv4Addr::new(169, 254, 0, 0)),
+            ("127.0.0.0/8", Ipv4Addr::new(127, 0, 0, 0)),
             ("100.64.0.0/10", Ipv4Addr::new(100, 64, 0, 0)),
             ("0.0.0.0/0", Ipv4Addr::new(0, 0, 0, 0)),
             ("0.0.0.0/0", Ipv4Addr::new(255, 255, 255, 255)),
@@ -374,4 +1286,549 @@ mod tests {
             assert!(result, "we expect {cidr:?} to contain {addr}");
         }
     }
+
+    #[test]
+    fn test_host_address() {
+        let addr = Ipv4Addr::new(192, 168, 0, 1);
+        let cidr = Ipv4Cidr::new_host(addr);
+
+        assert!(cidr.is_host());
+        assert_eq!(cidr.host_address(), Some(addr));
+    }
+
+    #[test]
+    fn test_no_host_address() {
+        let cidr = Ipv4Cidr::from_str("192.168.0.0/31").unwrap();
+
+        assert!(!cidr.is_host());
+        assert_eq!(cidr.host_address(), None);
+    }
+
+    #[test]
+    fn test_num_addresses() {
+        let test_cases = [
+            ("0.0.0.0/0", 1 << 32),
+            ("10.0.0.0/8", 1 << 24),
+            ("192.168.0.0/16", 65536),
+            ("192.168.200.4/30", 4),
+            ("192.168.200.4/31", 2),
+            ("192.168.200.4/32", 1),
+        ];
+
+        for (cidr_str, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            assert_eq!(
+                cidr.num_addresses(),
+                expected,
+                "we expect {cidr_str} to hold {expected} addresses"
+            );
+        }
+    }
+
+    #[test]
+    fn test_host_offset() {
+        let test_cases = [
+            ("192.168.1.0/24", "192.168.1.0", Some(0)),
+            ("192.168.1.0/24", "192.168.1.5", Some(5)),
+            ("192.168.1.0/24", "192.168.1.255", Some(255)),
+            ("192.168.1.0/24", "192.168.2.0", None),
+            ("192.168.1.0/24", "192.168.0.255", None),
+            ("10.0.0.1/32", "10.0.0.1", Some(0)),
+            ("0.0.0.0/0", "255.255.255.255", Some(u32::MAX)),
+        ];
+
+        for (cidr_str, addr, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let addr: Ipv4Addr = addr.parse().unwrap();
+
+            assert_eq!(
+                cidr.host_offset(addr),
+                expected,
+                "we expect {addr} to sit at offset {expected:?} within {cidr_str}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_peer_address() {
+        let test_cases = [
+            ("10.0.0.0/31", "10.0.0.0", Some("10.0.0.1")),
+            ("10.0.0.0/31", "10.0.0.1", Some("10.0.0.0")),
+            (
+                "255.255.255.254/31",
+                "255.255.255.255",
+                Some("255.255.255.254"),
+            ),
+            ("10.0.0.0/31", "10.0.0.2", None),
+            ("10.0.0.0/30", "10.0.0.1", None),
+            ("10.0.0.1/32", "10.0.0.1", None),
+        ];
+
+        for (cidr_str, addr, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let addr: Ipv4Addr = addr.parse().unwrap();
+            let expected = expected.map(|s| s.parse::<Ipv4Addr>().unwrap());
+
+            assert_eq!(
+                cidr.peer_address(addr),
+                expected,
+                "we expect the peer of {addr} within {cidr_str} to be {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_subnets() {
+        let test_cases = [
+            (
+                "192.168.1.0/24",
+                26,
+                vec![
+                    "192.168.1.0/26",
+                    "192.168.1.64/26",
+                    "192.168.1.128/26",
+                    "192.168.1.192/26",
+                ],
+            ),
+            ("192.168.1.0/24", 24, vec!["192.168.1.0/24"]),
+            ("192.168.1.0/24", 23, vec![]),
+            ("192.168.1.0/24", 33, vec![]),
+            ("0.0.0.0/0", 1, vec!["0.0.0.0/1", "128.0.0.0/1"]),
+            (
+                "255.255.255.252/30",
+                32,
+                vec![
+                    "255.255.255.252/32",
+                    "255.255.255.253/32",
+                    "255.255.255.254/32",
+                    "255.255.255.255/32",
+                ],
+            ),
+        ];
+
+        for (cidr_str, len, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let expected: Vec<Ipv4Cidr> = expected
+                .into_iter()
+                .map(|s| Ipv4Cidr::from_str(s).unwrap())
+                .collect();
+
+            assert_eq!(
+                cidr.subnets(len).collect::<Vec<_>>(),
+                expected,
+                "we expect /{len} subnets of {cidr_str} to be {expected:?}"
+            );
+        }
+
+        let mut all_hosts = Ipv4Cidr::from_str("0.0.0.0/0").unwrap().subnets(32);
+        assert_eq!(
+            all_hosts.next_back(),
+            Some(Ipv4Cidr::new_host(Ipv4Addr::new(255, 255, 255, 255)))
+        );
+    }
+
+    #[test]
+    fn test_to_slash24s() {
+        let test_cases = [
+            (
+                "10.1.0.0/22",
+                vec!["10.1.0.0/24", "10.1.1.0/24", "10.1.2.0/24", "10.1.3.0/24"],
+            ),
+            ("10.1.0.128/25", vec!["10.1.0.0/24"]),
+            ("10.1.0.7/32", vec!["10.1.0.0/24"]),
+            ("10.1.0.0/24", vec!["10.1.0.0/24"]),
+        ];
+
+        for (cidr_str, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let expected: Vec<Ipv4Cidr> = expected
+                .into_iter()
+                .map(|s| Ipv4Cidr::from_str(s).unwrap())
+                .collect();
+
+            assert_eq!(
+                cidr.to_slash24s(),
+                expected,
+                "we expect /24s of {cidr_str} to be {expected:?}"
+            );
+        }
+
+        assert_eq!(
+            Ipv4Cidr::from_str("10.0.0.0/8")
+                .unwrap()
+                .to_slash24s()
+                .len(),
+            65536
+        );
+    }
+
+    #[test]
+    fn test_index_within() {
+        let test_cases = [
+            ("192.168.1.0/26", "192.168.1.0/24", Some(0)),
+            ("192.168.1.64/26", "192.168.1.0/24", Some(1)),
+            ("192.168.1.128/26", "192.168.1.0/24", Some(2)),
+            ("192.168.1.192/26", "192.168.1.0/24", Some(3)),
+            ("10.0.3.0/24", "10.0.0.0/16", Some(3)),
+            ("255.255.255.255/32", "0.0.0.0/0", Some(u32::MAX)),
+            ("128.0.0.0/1", "0.0.0.0/0", Some(1)),
+            ("192.168.1.0/24", "192.168.1.0/24", None),
+            ("192.168.0.0/16", "192.168.1.0/24", None),
+            ("192.168.2.0/26", "192.168.1.0/24", None),
+        ];
+
+        for (cidr_str, parent_str, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let parent = Ipv4Cidr::from_str(parent_str).unwrap();
+
+            assert_eq!(
+                cidr.index_within(&parent),
+                expected,
+                "we expect index of {cidr_str} within {parent_str} to be {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_merge_with() {
+        let test_cases = [
+            ("10.0.0.0/25", "10.0.0.128/25", Some("10.0.0.0/24")),
+            ("10.0.0.128/25", "10.0.0.0/25", Some("10.0.0.0/24")),
+            ("0.0.0.0/1", "128.0.0.0/1", Some("0.0.0.0/0")),
+            ("10.0.0.0/25", "10.0.0.0/25", None),
+            ("10.0.0.0/25", "10.0.0.128/26", None),
+            ("10.0.0.128/25", "10.0.1.0/25", None),
+            ("0.0.0.0/0", "0.0.0.0/0", None),
+        ];
+
+        for (cidr_str, other_str, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let other = Ipv4Cidr::from_str(other_str).unwrap();
+            let expected = expected.map(|s| Ipv4Cidr::from_str(s).unwrap());
+
+            assert_eq!(
+                cidr.merge_with(&other),
+                expected,
+                "we expect {cidr_str} merged with {other_str} to be {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_try_merge_with() {
+        let test_cases = [
+            ("10.0.0.0/25", "10.0.0.128/25", Ok("10.0.0.0/24")),
+            ("10.0.0.128/25", "10.0.0.0/25", Ok("10.0.0.0/24")),
+            (
+                "10.0.0.0/25",
+                "10.0.0.128/26",
+                Err(MergeError::DifferentLength),
+            ),
+            (
+                "10.0.0.0/24",
+                "10.0.0.0/25",
+                Err(MergeError::DifferentLength),
+            ),
+            (
+                "10.0.0.128/25",
+                "10.0.1.0/25",
+                Err(MergeError::MisalignedParent),
+            ),
+            (
+                "10.0.1.0/25",
+                "10.0.0.128/25",
+                Err(MergeError::MisalignedParent),
+            ),
+            ("10.0.0.0/25", "10.0.2.0/25", Err(MergeError::NotSiblings)),
+            ("10.0.0.0/25", "10.0.0.0/25", Err(MergeError::NotSiblings)),
+            ("0.0.0.0/0", "0.0.0.0/0", Err(MergeError::NotSiblings)),
+        ];
+
+        for (cidr_str, other_str, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let other = Ipv4Cidr::from_str(other_str).unwrap();
+            let expected = expected.map(|s| Ipv4Cidr::from_str(s).unwrap());
+
+            assert_eq!(
+                cidr.try_merge_with(&other),
+                expected,
+                "we expect {cidr_str} merged with {other_str} to be {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sibling() {
+        let test_cases = [
+            ("10.0.0.0/25", Some("10.0.0.128/25")),
+            ("10.0.0.128/25", Some("10.0.0.0/25")),
+            ("10.0.0.5/32", Some("10.0.0.4/32")),
+            ("128.0.0.0/1", Some("0.0.0.0/1")),
+            ("0.0.0.0/0", None),
+        ];
+
+        for (cidr_str, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let expected = expected.map(|s| Ipv4Cidr::from_str(s).unwrap());
+
+            assert_eq!(
+                cidr.sibling(),
+                expected,
+                "we expect the sibling of {cidr_str} to be {expected:?}"
+            );
+            if let Some(sibling) = expected {
+                assert!(cidr.is_sibling_of(&sibling));
+                assert!(sibling.is_sibling_of(&cidr));
+            }
+        }
+
+        let cidr = Ipv4Cidr::from_str("10.0.0.0/25").unwrap();
+        assert!(!cidr.is_sibling_of(&cidr));
+        assert!(!cidr.is_sibling_of(&Ipv4Cidr::from_str("10.0.1.0/25").unwrap()));
+        assert!(!cidr.is_sibling_of(&Ipv4Cidr::from_str("10.0.0.128/26").unwrap()));
+    }
+
+    #[test]
+    fn test_common_supernet() {
+        let test_cases = [
+            ("10.0.0.0/25", "10.0.0.128/25", "10.0.0.0/24"),
+            ("10.1.2.0/24", "10.1.3.0/24", "10.1.2.0/23"),
+            ("10.1.2.0/24", "10.0.0.0/8", "10.0.0.0/8"),
+            ("10.1.2.0/24", "10.1.2.0/24", "10.1.2.0/24"),
+            ("10.0.0.0/8", "192.168.0.0/16", "0.0.0.0/0"),
+        ];
+
+        for (cidr_str, other_str, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let other = Ipv4Cidr::from_str(other_str).unwrap();
+            let expected = Ipv4Cidr::from_str(expected).unwrap();
+
+            assert_eq!(
+                cidr.common_supernet(&other),
+                expected,
+                "we expect common supernet of {cidr_str} and {other_str} to be {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_exclude() {
+        let test_cases = [
+            ("10.0.0.0/24", "10.0.0.0/24", vec![]),
+            ("10.0.0.0/24", "10.0.0.0/16", vec![]),
+            ("10.0.0.0/24", "10.0.1.0/24", vec!["10.0.0.0/24"]),
+            ("10.0.0.0/24", "10.0.0.0/25", vec!["10.0.0.128/25"]),
+            (
+                "10.0.0.0/24",
+                "10.0.0.64/26",
+                vec!["10.0.0.0/26", "10.0.0.128/25"],
+            ),
+            ("0.0.0.0/0", "128.0.0.0/2", vec!["0.0.0.0/1", "192.0.0.0/2"]),
+        ];
+
+        for (cidr_str, other_str, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let other = Ipv4Cidr::from_str(other_str).unwrap();
+            let expected: Vec<Ipv4Cidr> = expected
+                .into_iter()
+                .map(|s| Ipv4Cidr::from_str(s).unwrap())
+                .collect();
+
+            assert_eq!(
+                cidr.exclude(&other),
+                expected,
+                "we expect {cidr_str} without {other_str} to be {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_complement() {
+        let test_cases = [
+            (
+                "10.0.0.0/8",
+                "0.0.0.0/0",
+                vec![
+                    "0.0.0.0/5",
+                    "8.0.0.0/7",
+                    "11.0.0.0/8",
+                    "12.0.0.0/6",
+                    "16.0.0.0/4",
+                    "32.0.0.0/3",
+                    "64.0.0.0/2",
+                    "128.0.0.0/1",
+                ],
+            ),
+            ("10.0.0.0/8", "10.0.0.0/7", vec!["11.0.0.0/8"]),
+            ("10.0.0.0/8", "10.0.0.0/8", vec![]),
+            ("10.0.0.0/8", "10.1.0.0/16", vec![]),
+            ("10.0.0.0/8", "192.168.0.0/16", vec!["192.168.0.0/16"]),
+        ];
+
+        for (cidr_str, scope_str, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let scope = Ipv4Cidr::from_str(scope_str).unwrap();
+            let expected: Vec<Ipv4Cidr> = expected
+                .into_iter()
+                .map(|s| Ipv4Cidr::from_str(s).unwrap())
+                .collect();
+
+            assert_eq!(
+                cidr.complement(scope),
+                expected,
+                "we expect complement of {cidr_str} within {scope_str} to be {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_around() {
+        let cidr = Ipv4Cidr::from_str("10.0.0.0/24").unwrap();
+        let excluded = Ipv4Addr::new(10, 0, 0, 5);
+        let parts = cidr.split_around(excluded);
+
+        for last_octet in 0..=255 {
+            let addr = Ipv4Addr::new(10, 0, 0, last_octet);
+            let covering = parts.iter().filter(|part| part.contains(addr)).count();
+            let expected = if addr == excluded { 0 } else { 1 };
+
+            assert_eq!(
+                covering, expected,
+                "we expect {addr} to be covered by {expected} parts of {parts:?}"
+            );
+        }
+
+        let outside = Ipv4Addr::new(10, 0, 1, 5);
+        assert_eq!(cidr.split_around(outside), vec![cidr]);
+    }
+}
+
+#[cfg(test)]
+mod host_tests {
+    use super::Ipv4Cidr;
+    use std::{net::Ipv4Addr, str::FromStr};
+
+    #[test]
+    fn test_slash_30_hosts() {
+        let cidr = Ipv4Cidr::from_str("192.168.200.4/30").unwrap();
+
+        assert_eq!(cidr.network(), Ipv4Addr::new(192, 168, 200, 4));
+        assert_eq!(cidr.broadcast(), Some(Ipv4Addr::new(192, 168, 200, 7)));
+        assert_eq!(cidr.usable_hosts(), 2);
+        assert_eq!(
+            cidr.hosts().collect::<Vec<_>>(),
+            vec![
+                Ipv4Addr::new(192, 168, 200, 5),
+                Ipv4Addr::new(192, 168, 200, 6)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_valid_hosts() {
+        let test_cases = [
+            ("192.168.1.0/24", Ipv4Addr::new(192, 168, 1, 0), false),
+            ("192.168.1.0/24", Ipv4Addr::new(192, 168, 1, 1), true),
+            ("192.168.1.0/24", Ipv4Addr::new(192, 168, 1, 128), true),
+            ("192.168.1.0/24", Ipv4Addr::new(192, 168, 1, 254), true),
+            ("192.168.1.0/24", Ipv4Addr::new(192, 168, 1, 255), false),
+            ("192.168.1.0/24", Ipv4Addr::new(192, 168, 2, 1), false),
+            ("192.168.200.4/31", Ipv4Addr::new(192, 168, 200, 4), true),
+            ("192.168.200.4/31", Ipv4Addr::new(192, 168, 200, 5), true),
+            ("192.168.200.4/31", Ipv4Addr::new(192, 168, 200, 6), false),
+            ("192.168.200.4/32", Ipv4Addr::new(192, 168, 200, 4), true),
+            ("192.168.200.4/32", Ipv4Addr::new(192, 168, 200, 5), false),
+        ];
+
+        for (cidr_str, addr, expected) in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+
+            assert_eq!(
+                cidr.is_valid_host(addr),
+                expected,
+                "we expect {addr} validity as host of {cidr_str} to be {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_slash_30_reverse() {
+        let cidr = Ipv4Cidr::from_str("192.168.200.4/30").unwrap();
+
+        assert_eq!(
+            cidr.addresses().collect::<Vec<_>>(),
+            (4..=7)
+                .map(|last| Ipv4Addr::new(192, 168, 200, last))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            cidr.addresses_rev().collect::<Vec<_>>(),
+            (4..=7)
+                .rev()
+                .map(|last| Ipv4Addr::new(192, 168, 200, last))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            cidr.hosts_rev().collect::<Vec<_>>(),
+            vec![
+                Ipv4Addr::new(192, 168, 200, 6),
+                Ipv4Addr::new(192, 168, 200, 5)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slash_31_hosts() {
+        let cidr = Ipv4Cidr::from_str("192.168.200.4/31").unwrap();
+
+        assert_eq!(cidr.network(), Ipv4Addr::new(192, 168, 200, 4));
+        assert_eq!(cidr.broadcast(), None);
+        assert_eq!(cidr.usable_hosts(), 2);
+        assert_eq!(
+            cidr.hosts().collect::<Vec<_>>(),
+            vec![
+                Ipv4Addr::new(192, 168, 200, 4),
+                Ipv4Addr::new(192, 168, 200, 5)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slash_32_hosts() {
+        let addr = Ipv4Addr::new(192, 168, 200, 4);
+        let cidr = Ipv4Cidr::new_host(addr);
+
+        assert_eq!(cidr.network(), addr);
+        assert_eq!(cidr.broadcast(), Some(addr));
+        assert_eq!(cidr.usable_hosts(), 1);
+        assert_eq!(cidr.hosts().collect::<Vec<_>>(), vec![addr]);
+    }
+
+    #[test]
+    fn test_default_route_reverse() {
+        let cidr = Ipv4Cidr::from_str("0.0.0.0/0").unwrap();
+
+        assert_eq!(
+            cidr.addresses_rev().next(),
+            Some(Ipv4Addr::new(255, 255, 255, 255))
+        );
+        assert_eq!(
+            cidr.hosts_rev().next(),
+            Some(Ipv4Addr::new(255, 255, 255, 254))
+        );
+    }
+
+    #[test]
+    fn test_default_route_hosts() {
+        let cidr = Ipv4Cidr::from_str("0.0.0.0/0").unwrap();
+
+        assert_eq!(cidr.network(), Ipv4Addr::new(0, 0, 0, 0));
+        assert_eq!(cidr.broadcast(), Some(Ipv4Addr::new(255, 255, 255, 255)));
+        assert_eq!(cidr.usable_hosts(), u64::from(u32::MAX) - 1);
+        assert_eq!(cidr.hosts().next(), Some(Ipv4Addr::new(0, 0, 0, 1)));
+        assert_eq!(
+            cidr.hosts().next_back(),
+            Some(Ipv4Addr::new(255, 255, 255, 254))
+        );
+    }
 }
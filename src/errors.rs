@@ -6,4 +6,15 @@ pub enum NetworkParseError {
     ParseIntError(ParseIntError),
     CidrParseError,
     NetworkLengthError,
+    PrefixTooLong,
+    OverlappingCidr,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum MergeError {
+    DifferentLength,
+    // Equal, overlapping or non-adjacent blocks, and the default route, which has no sibling.
+    NotSiblings,
+    // Adjacent blocks of the same length whose union is not an aligned block.
+    MisalignedParent,
 }
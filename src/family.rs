@@ -0,0 +1,54 @@
+use crate::errors::NetworkParseError;
+
+pub trait AddrFamily {
+    const MAX_LENGTH: u8;
+
+    fn validate_len(len: u8) -> Result<u8, NetworkParseError> {
+        if len > Self::MAX_LENGTH {
+            Err(NetworkParseError::NetworkLengthError)
+        } else {
+            Ok(len)
+        }
+    }
+}
+
+pub struct Ipv4;
+
+pub struct Ipv6;
+
+impl AddrFamily for Ipv4 {
+    const MAX_LENGTH: u8 = 32;
+}
+
+impl AddrFamily for Ipv6 {
+    const MAX_LENGTH: u8 = 128;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AddrFamily, Ipv4, Ipv6};
+    use crate::errors::NetworkParseError;
+
+    #[test]
+    fn test_family_boundaries() {
+        let test_cases = [
+            (Ipv4::validate_len(0), Ok(0)),
+            (Ipv4::validate_len(32), Ok(32)),
+            (
+                Ipv4::validate_len(33),
+                Err(NetworkParseError::NetworkLengthError),
+            ),
+            (Ipv6::validate_len(32), Ok(32)),
+            (Ipv6::validate_len(64), Ok(64)),
+            (Ipv6::validate_len(128), Ok(128)),
+            (
+                Ipv6::validate_len(129),
+                Err(NetworkParseError::NetworkLengthError),
+            ),
+        ];
+
+        for (result, expected) in test_cases {
+            assert_eq!(result, expected);
+        }
+    }
+}
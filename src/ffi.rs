@@ -0,0 +1,79 @@
+use crate::{errors::NetworkParseError, Ipv4Cidr};
+use std::net::Ipv4Addr;
+
+#[repr(C)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Ipv4CidrC {
+    pub network: u32,
+    pub prefix_len: u8,
+}
+
+impl From<Ipv4Cidr> for Ipv4CidrC {
+    fn from(cidr: Ipv4Cidr) -> Self {
+        Self {
+            network: u32::from(cidr.min()),
+            prefix_len: cidr.prefix_len(),
+        }
+    }
+}
+
+impl TryFrom<Ipv4CidrC> for Ipv4Cidr {
+    type Error = NetworkParseError;
+
+    fn try_from(cidr: Ipv4CidrC) -> Result<Self, Self::Error> {
+        Ipv4Cidr::new(Ipv4Addr::from(cidr.network), cidr.prefix_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ipv4CidrC;
+    use crate::{errors::NetworkParseError, Ipv4Cidr};
+    use std::{net::Ipv4Addr, str::FromStr};
+
+    #[test]
+    fn test_round_trip_through_c_repr() {
+        let test_cases = [
+            "0.0.0.0/0",
+            "10.0.0.0/8",
+            "192.168.0.0/16",
+            "192.168.200.4/30",
+            "127.0.0.1/32",
+        ];
+
+        for cidr_str in test_cases {
+            let cidr = Ipv4Cidr::from_str(cidr_str).unwrap();
+            let c_repr = Ipv4CidrC::from(cidr);
+
+            assert_eq!(c_repr.network, u32::from(cidr.min()));
+            assert_eq!(c_repr.prefix_len, cidr.prefix_len());
+            assert_eq!(
+                Ipv4Cidr::try_from(c_repr),
+                Ok(cidr),
+                "we expect {cidr_str} to survive a round trip through the C representation"
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_c_repr() {
+        let test_cases = [
+            Ipv4CidrC {
+                network: u32::from(Ipv4Addr::new(192, 168, 0, 1)),
+                prefix_len: 16,
+            },
+            Ipv4CidrC {
+                network: 0,
+                prefix_len: 33,
+            },
+        ];
+
+        for c_repr in test_cases {
+            assert_eq!(
+                Ipv4Cidr::try_from(c_repr),
+                Err(NetworkParseError::NetworkLengthError),
+                "we expect {c_repr:?} to be rejected"
+            );
+        }
+    }
+}
@@ -1,8 +1,33 @@
-pub use cidr::Ipv4Cidr;
-pub use routing_table::{HashRoutingTable, ListRoutingTable, RoutingTable, TrieRoutingTable};
-pub use utils::get_cidr_mask;
+pub use acl::{resolve_acl, AclDecision};
+pub use aggregation::{
+    aggregate, cover_addresses, enclosing_cidr, gap_between, range_to_cidrs, verify_aggregation,
+    AggregationMap,
+};
+pub use cidr::{cidr_str_eq, cmp_by_size, parse_cidr_list, CidrRelation, CidrStyle, Ipv4Cidr};
+pub use errors::{MergeError, NetworkParseError};
+pub use family::{AddrFamily, Ipv4, Ipv6};
+#[cfg(feature = "ffi")]
+pub use ffi::Ipv4CidrC;
+pub use loader::{load_cidrs, par_load_cidrs};
+pub use prefix_len::PrefixLen;
+pub use routing_table::{
+    build_auto, difference, intersection, new_table, union, AddrRole, HashRoutingTable,
+    InstrumentedRoutingTable, IntervalRoutingTable, ListRoutingTable, LookupStats,
+    ObservableRoutingTable, PriorityListRoutingTable, RefCountedRoutingTable, RouteEvent,
+    RoutingTable, RoutingTableMap, TableKind, TrieRoutingTable, FIB_DESCEND, FIB_NO_ROUTE,
+};
+pub use utils::{divergence_prefix_len, get_cidr_mask};
 
+mod acl;
+mod aggregation;
 mod cidr;
 mod errors;
+mod family;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod loader;
+mod prefix_len;
+#[cfg(feature = "rand")]
+mod random;
 mod routing_table;
 mod utils;
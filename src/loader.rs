@@ -0,0 +1,107 @@
+use crate::{errors::NetworkParseError, Ipv4Cidr};
+use std::str::FromStr;
+
+type LoadResult = (Vec<Ipv4Cidr>, Vec<(usize, NetworkParseError)>);
+
+// Blank lines are skipped; errors carry the index of the offending line.
+pub fn load_cidrs(lines: &[&str]) -> LoadResult {
+    split_results(lines.iter().enumerate().filter_map(parse_line))
+}
+
+// Parsing runs on the rayon pool when the `rayon` feature is enabled and falls back to
+// `load_cidrs` otherwise. Results keep the input order either way.
+pub fn par_load_cidrs(lines: &[&str]) -> LoadResult {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        let parsed: Vec<(usize, Result<Ipv4Cidr, NetworkParseError>)> = lines
+            .par_iter()
+            .enumerate()
+            .filter_map(parse_line)
+            .collect();
+
+        split_results(parsed.into_iter())
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        load_cidrs(lines)
+    }
+}
+
+fn parse_line((idx, line): (usize, &&str)) -> Option<(usize, Result<Ipv4Cidr, NetworkParseError>)> {
+    let line = line.trim();
+
+    (!line.is_empty()).then(|| (idx, Ipv4Cidr::from_str(line)))
+}
+
+fn split_results(
+    parsed: impl Iterator<Item = (usize, Result<Ipv4Cidr, NetworkParseError>)>,
+) -> LoadResult {
+    let mut cidrs = Vec::new();
+    let mut errors = Vec::new();
+
+    for (idx, result) in parsed {
+        match result {
+            Ok(cidr) => cidrs.push(cidr),
+            Err(err) => errors.push((idx, err)),
+        }
+    }
+
+    (cidrs, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_cidrs, par_load_cidrs};
+    use crate::{errors::NetworkParseError, Ipv4Cidr};
+    use std::{net::Ipv4Addr, str::FromStr};
+
+    #[test]
+    fn test_load_cidrs() {
+        let lines = [
+            "10.0.0.0/8",
+            "",
+            " 192.168.0.0/16 ",
+            "192.168.0.1",
+            "172.16.0.0/33",
+            "0.0.0.0/0",
+        ];
+
+        let (cidrs, errors) = load_cidrs(&lines);
+
+        assert_eq!(
+            cidrs,
+            vec![
+                Ipv4Cidr::from_str("10.0.0.0/8").unwrap(),
+                Ipv4Cidr::from_str("192.168.0.0/16").unwrap(),
+                Ipv4Cidr::from_str("0.0.0.0/0").unwrap(),
+            ]
+        );
+        assert_eq!(
+            errors,
+            vec![
+                (3, NetworkParseError::CidrParseError),
+                (4, NetworkParseError::NetworkLengthError),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "crossbeam-epoch, used by rayon, fails Stacked Borrows")]
+    fn test_par_load_cidrs_matches_serial() {
+        let owned: Vec<String> = (0..10_000u32)
+            .map(|idx| match idx % 7 {
+                0 => String::from("garbage"),
+                1 => String::new(),
+                2 => format!("{}/33", Ipv4Addr::from(idx)),
+                _ => format!("{}/32", Ipv4Addr::from(idx.wrapping_mul(2_654_435_761))),
+            })
+            .collect();
+        let lines: Vec<&str> = owned.iter().map(String::as_str).collect();
+
+        assert_eq!(par_load_cidrs(&lines), load_cidrs(&lines));
+        assert_eq!(par_load_cidrs(&[]), (vec![], vec![]));
+    }
+}
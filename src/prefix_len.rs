@@ -0,0 +1,70 @@
+use crate::{
+    family::{AddrFamily, Ipv4},
+    utils::{get_cidr_mask, MAX_LENGTH},
+};
+use std::fmt;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Hash)]
+pub struct PrefixLen(u8);
+
+impl PrefixLen {
+    pub fn new(len: u8) -> Option<Self> {
+        Ipv4::validate_len(len).ok().map(Self)
+    }
+
+    /// The caller guarantees `len <= 32`; out-of-range lengths only panic in debug builds.
+    pub const fn new_unchecked(len: u8) -> Self {
+        debug_assert!(len <= MAX_LENGTH);
+        Self(len)
+    }
+
+    pub fn get(self) -> u8 {
+        self.0
+    }
+
+    pub fn mask(self) -> u32 {
+        get_cidr_mask(self.0).expect("PrefixLen is always at most 32")
+    }
+}
+
+impl From<PrefixLen> for u8 {
+    fn from(len: PrefixLen) -> Self {
+        len.0
+    }
+}
+
+impl fmt::Display for PrefixLen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "/{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PrefixLen;
+
+    #[test]
+    fn test_prefix_len_bounds() {
+        for len in 0..=32 {
+            let prefix = PrefixLen::new(len).expect("we expect lengths up to 32 to be valid");
+
+            assert_eq!(prefix.get(), len);
+            assert_eq!(u8::from(prefix), len);
+        }
+
+        for len in [33, 64, u8::MAX] {
+            assert_eq!(PrefixLen::new(len), None, "we expect /{len} to be invalid");
+        }
+    }
+
+    #[test]
+    fn test_prefix_len_mask() {
+        const HOST: PrefixLen = PrefixLen::new_unchecked(32);
+
+        assert_eq!(HOST.mask(), u32::MAX);
+        assert_eq!(PrefixLen::new(24).unwrap().mask(), 0xffff_ff00);
+        assert_eq!(PrefixLen::new(0).unwrap().mask(), 0);
+        assert_eq!(PrefixLen::new(8).unwrap().to_string(), "/8");
+        assert!(PrefixLen::new(8) < PrefixLen::new(16));
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{utils::get_cidr_mask, Ipv4Cidr};
+use rand::Rng;
+use std::net::Ipv4Addr;
+
+impl Ipv4Cidr {
+    pub fn random<R: Rng>(rng: &mut R) -> Ipv4Cidr {
+        let len = rng.gen_range(0..=32);
+        let mask = get_cidr_mask(len).expect("Len should be smaller than equal to 32");
+
+        Ipv4Cidr::from_bits(rng.gen::<u32>() & mask, len).expect("Input is correct")
+    }
+
+    pub fn random_address<R: Rng>(&self, rng: &mut R) -> Ipv4Addr {
+        let bits = rng.gen_range(u32::from(self.min())..=u32::from(self.max()));
+
+        Ipv4Addr::from(bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Ipv4Cidr;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_random_cidrs_are_valid() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..1000 {
+            let cidr = Ipv4Cidr::random(&mut rng);
+
+            assert_eq!(
+                Ipv4Cidr::new(cidr.min(), cidr.prefix_len()),
+                Ok(cidr),
+                "we expect {cidr:?} to be a valid cidr"
+            );
+        }
+    }
+
+    #[test]
+    fn test_random_address_is_contained() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..1000 {
+            let cidr = Ipv4Cidr::random(&mut rng);
+            let addr = cidr.random_address(&mut rng);
+
+            assert!(cidr.contains(addr), "we expect {cidr:?} to contain {addr}");
+        }
+    }
+}
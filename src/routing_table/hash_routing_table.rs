@@ -1,35 +1,67 @@
-use crate::{utils::cut_addr, Ipv4Cidr, RoutingTable};
-use std::{collections::HashSet, net::Ipv4Addr};
+use crate::{utils::get_cidr_mask, Ipv4Cidr, RoutingTable};
+use std::{
+    collections::{hash_map::RandomState, HashSet},
+    hash::BuildHasher,
+    net::Ipv4Addr,
+};
 
-pub struct HashRoutingTable {
-    cidrs: Vec<HashSet<u32>>,
+pub struct HashRoutingTable<S = RandomState> {
+    cidrs: Vec<HashSet<u32, S>>,
+    // Bit `len` is set while the `/len` bucket is non-empty.
+    occupied: u64,
 }
 
 impl HashRoutingTable {
     pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<S: BuildHasher + Clone> HashRoutingTable<S> {
+    pub fn with_hasher(hash_builder: S) -> Self {
         let mut cidrs = Vec::with_capacity(33);
         for _ in 0..=32 {
-            cidrs.push(HashSet::new());
+            cidrs.push(HashSet::with_hasher(hash_builder.clone()));
         }
 
-        Self { cidrs }
+        Self { cidrs, occupied: 0 }
     }
 }
 
-impl RoutingTable for HashRoutingTable {
-    fn add_cidr(&mut self, cidr: Ipv4Cidr) {
-        self.cidrs[cidr.prefix_len() as usize].insert(u32::from(cidr.min()));
-    }
+impl<S: BuildHasher> HashRoutingTable<S> {
+    pub fn into_sorted_vec(self) -> Vec<Ipv4Cidr> {
+        let mut pairs: Vec<(u32, u8)> = self
+            .cidrs
+            .into_iter()
+            .zip(0..)
+            .flat_map(|(bucket, len)| bucket.into_iter().map(move |bits| (bits, len)))
+            .collect();
+        pairs.sort_unstable();
 
-    fn remove_cidr(&mut self, cidr: Ipv4Cidr) {
-        self.cidrs[cidr.prefix_len() as usize].remove(&u32::from(cidr.min()));
+        pairs
+            .into_iter()
+            .map(|(bits, len)| {
+                Ipv4Cidr::from_bits(bits, len).expect("Len and Ipv4Addr should always be valid.")
+            })
+            .collect()
     }
 
-    fn find_exact_cidr(&self, addr: Ipv4Addr) -> Option<Ipv4Cidr> {
-        let mut bit_mask = u32::MAX;
-        let mut addr_bits = u32::from(addr);
+    // The scan starts at the longest occupied length up to `max_len`, so empty long buckets
+    // cost nothing. Below that every bucket is probed: a lookup in an empty set returns
+    // before hashing, and checking `occupied` on every step costs more than it saves.
+    // `bit_mask` only ever shifts by one: shifting `get_cidr_mask(len)` left once gives
+    // `get_cidr_mask(len - 1)`, so no shift-by-32 happens on this path.
+    fn find_longest(&self, bits: u32, max_len: u8) -> Option<Ipv4Cidr> {
+        let candidates = self.occupied & (u64::MAX >> (63 - max_len));
+        if candidates == 0 {
+            return None;
+        }
+
+        let longest = (63 - candidates.leading_zeros()) as u8;
+        let mut bit_mask = get_cidr_mask(longest).expect("Longest len should always be valid.");
+        let mut addr_bits = bits;
 
-        for len in (0..=32).rev() {
+        for len in (0..=longest).rev() {
             addr_bits &= bit_mask;
             bit_mask <<= 1;
 
@@ -43,16 +75,152 @@ impl RoutingTable for HashRoutingTable {
 
         None
     }
+}
+
+impl<S: BuildHasher + Clone + Default> Default for HashRoutingTable<S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<S: BuildHasher> RoutingTable for HashRoutingTable<S> {
+    fn add_cidr(&mut self, cidr: Ipv4Cidr) {
+        let len = cidr.prefix_len();
+
+        self.cidrs[len as usize].insert(u32::from(cidr.min()));
+        self.occupied |= 1 << len;
+    }
+
+    fn remove_cidr(&mut self, cidr: Ipv4Cidr) {
+        let len = cidr.prefix_len();
+        let bucket = &mut self.cidrs[len as usize];
+
+        bucket.remove(&u32::from(cidr.min()));
+        if bucket.is_empty() {
+            self.occupied &= !(1 << len);
+        }
+    }
+
+    fn find_exact_cidr(&self, addr: Ipv4Addr) -> Option<Ipv4Cidr> {
+        self.find_longest(u32::from(addr), 32)
+    }
+
+    fn get(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.cidrs[cidr.prefix_len() as usize]
+            .contains(&u32::from(cidr.min()))
+            .then_some(cidr)
+    }
+
+    // Any match answers the question, so occupied buckets are probed shortest first and a
+    // default route is a single probe.
+    fn is_routable(&self, addr: Ipv4Addr) -> bool {
+        let bits = u32::from(addr);
+        let mut candidates = self.occupied;
+
+        while candidates != 0 {
+            let len = candidates.trailing_zeros() as u8;
+            candidates &= candidates - 1;
+
+            let addr_bits = bits & get_cidr_mask(len).expect("Len should always be valid.");
+            if self.cidrs[len as usize].contains(&addr_bits) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn longest_prefix_match_capped(&self, addr: Ipv4Addr, max_len: u8) -> Option<Ipv4Cidr> {
+        self.find_longest(u32::from(addr), max_len.min(32))
+    }
+
+    fn find_covering(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.find_longest(u32::from(cidr.min()), cidr.prefix_len())
+    }
+
+    fn find_parent(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        match cidr.prefix_len() {
+            0 => None,
+            len => self.find_longest(u32::from(cidr.min()), len - 1),
+        }
+    }
+
+    fn match_path(&self, addr: Ipv4Addr) -> Vec<Ipv4Cidr> {
+        let addr_bits = u32::from(addr);
+
+        (0..=32)
+            .filter_map(|len| {
+                let bits = addr_bits & get_cidr_mask(len).expect("Len should always be valid.");
+
+                self.cidrs[len as usize].contains(&bits).then(|| {
+                    Ipv4Cidr::from_bits(bits, len)
+                        .expect("Len and Ipv4Addr should always be valid.")
+                })
+            })
+            .collect()
+    }
+
+    fn routes_of_length(&self, len: u8) -> Vec<Ipv4Cidr> {
+        let Some(bucket) = self.cidrs.get(len as usize) else {
+            return Vec::new();
+        };
+
+        let mut bits: Vec<u32> = bucket.iter().copied().collect();
+        bits.sort_unstable();
+        bits.into_iter()
+            .map(|bits| {
+                Ipv4Cidr::from_bits(bits, len).expect("Len and Ipv4Addr should always be valid.")
+            })
+            .collect()
+    }
 
     fn size(&self) -> usize {
         self.cidrs.iter().map(|s| s.len()).sum()
     }
+
+    fn approx_heap_bytes(&self) -> usize {
+        let buckets = self.cidrs.capacity() * size_of::<HashSet<u32, S>>();
+        // Every slot holds the key plus one control byte.
+        let entries: usize = self
+            .cidrs
+            .iter()
+            .map(|bucket| bucket.capacity() * (size_of::<u32>() + 1))
+            .sum();
+
+        buckets + entries
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Ipv4Cidr> + '_> {
+        Box::new(self.cidrs.iter().enumerate().flat_map(|(len, bucket)| {
+            bucket.iter().map(move |&bits| {
+                Ipv4Cidr::from_bits(bits, len as u8)
+                    .expect("Len and Ipv4Addr should always be valid.")
+            })
+        }))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::HashRoutingTable;
-    use crate::routing_table::tests::{complex_test, empty_test, simple_test, one_global_cidr};
+    use crate::routing_table::tests::{
+        add_cidr_max_len_test, aggregated_test, aggregates_exceeding_test, approx_heap_bytes_test,
+        best_fit_test, cidrs_sorted_test, classify_address_test, complement_within_test,
+        complex_test, count_within_test, coverage_test, covered_addresses_test, covers_test,
+        default_free_test, drain_matching_test, duplicate_add_test, empty_test,
+        filter_routable_test, find_by_range_test, find_parent_test, fingerprint_test, get_test,
+        has_same_cidrs_test, hierarchy_neighbors_test, is_routable_test, iter_with_depth_test,
+        leaf_pairs_be_test, leaf_pairs_test, longest_prefix_match_capped_test,
+        longest_prefix_match_detailed_test, lookup_or_default_test, match_as_tcam_test,
+        match_path_test, match_with_backup_test, next_free_subnet_test, one_global_cidr,
+        remove_cidrs_test, remove_within_test, routes_in_range_test, routes_of_length_test,
+        simple_test, sorted_cidrs, to_fib_24_test, unsorted_cidrs,
+    };
+    use crate::{Ipv4Cidr, RoutingTable};
+    use std::{
+        hash::{BuildHasherDefault, Hasher},
+        net::Ipv4Addr,
+    };
 
     #[test]
     fn test_hash_empty_case() {
@@ -73,4 +241,367 @@ mod tests {
     fn test_hash_complex() {
         complex_test(Box::new(HashRoutingTable::new()))
     }
+
+    #[test]
+    fn test_hash_find_parent() {
+        find_parent_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_covers() {
+        covers_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_match_path() {
+        match_path_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_only_default_route() {
+        let mut routing_table = HashRoutingTable::new();
+        let default_route = Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap();
+        routing_table.add_cidr(default_route);
+
+        for addr in [
+            Ipv4Addr::new(0, 0, 0, 0),
+            Ipv4Addr::new(0, 0, 0, 1),
+            Ipv4Addr::new(128, 0, 0, 0),
+            Ipv4Addr::new(192, 168, 1, 1),
+            Ipv4Addr::new(255, 255, 255, 255),
+        ] {
+            assert_eq!(
+                routing_table.find_exact_cidr(addr),
+                Some(default_route),
+                "we expect {addr} to fall back to the default route"
+            );
+        }
+
+        assert_eq!(
+            routing_table.find_covering(default_route),
+            Some(default_route)
+        );
+        assert_eq!(routing_table.find_parent(default_route), None);
+        assert_eq!(
+            routing_table.find_parent(Ipv4Cidr::new(Ipv4Addr::new(128, 0, 0, 0), 1).unwrap()),
+            Some(default_route)
+        );
+    }
+
+    #[test]
+    fn test_hash_occupied_lengths() {
+        let mut routing_table = HashRoutingTable::new();
+        let aggregate = Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap();
+        let first = Ipv4Cidr::new(Ipv4Addr::new(10, 1, 1, 0), 24).unwrap();
+        let second = Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 24).unwrap();
+
+        for cidr in [aggregate, first, second] {
+            routing_table.add_cidr(cidr);
+        }
+        assert_eq!(routing_table.occupied, (1 << 8) | (1 << 24));
+
+        routing_table.remove_cidr(first);
+        assert_eq!(routing_table.occupied, (1 << 8) | (1 << 24));
+        assert_eq!(
+            routing_table.find_exact_cidr(Ipv4Addr::new(10, 1, 2, 1)),
+            Some(second)
+        );
+        assert_eq!(
+            routing_table.find_exact_cidr(Ipv4Addr::new(10, 1, 1, 1)),
+            Some(aggregate)
+        );
+
+        routing_table.remove_cidr(second);
+        routing_table.remove_cidr(second);
+        assert_eq!(routing_table.occupied, 1 << 8);
+        assert_eq!(routing_table.find_covering(second), Some(aggregate));
+        assert_eq!(
+            routing_table.find_covering(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 7).unwrap()),
+            None
+        );
+        assert_eq!(
+            routing_table.find_exact_cidr(Ipv4Addr::new(11, 0, 0, 1)),
+            None
+        );
+
+        routing_table.remove_cidr(aggregate);
+        assert_eq!(routing_table.occupied, 0);
+        assert_eq!(
+            routing_table.find_exact_cidr(Ipv4Addr::new(10, 1, 2, 1)),
+            None
+        );
+    }
+
+    #[derive(Default)]
+    struct IdentityHasher(u64);
+
+    impl Hasher for IdentityHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for byte in bytes {
+                self.0 = (self.0 << 8) | u64::from(*byte);
+            }
+        }
+
+        fn write_u32(&mut self, i: u32) {
+            self.0 = u64::from(i);
+        }
+    }
+
+    #[test]
+    fn test_hash_with_custom_hasher() {
+        let hasher = BuildHasherDefault::<IdentityHasher>::default();
+
+        empty_test(Box::new(HashRoutingTable::with_hasher(hasher.clone())));
+        one_global_cidr(Box::new(HashRoutingTable::with_hasher(hasher.clone())));
+        simple_test(Box::new(HashRoutingTable::with_hasher(hasher.clone())));
+        complex_test(Box::new(HashRoutingTable::with_hasher(hasher)));
+        match_path_test(Box::new(HashRoutingTable::<
+            BuildHasherDefault<IdentityHasher>,
+        >::default()));
+    }
+
+    #[test]
+    fn test_hash_get() {
+        get_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_coverage() {
+        coverage_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_hierarchy_neighbors() {
+        hierarchy_neighbors_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_add_cidr_max_len() {
+        add_cidr_max_len_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_drain_matching() {
+        drain_matching_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_next_free_subnet() {
+        next_free_subnet_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_classify_address() {
+        classify_address_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_remove_within() {
+        remove_within_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_approx_heap_bytes() {
+        approx_heap_bytes_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_fingerprint() {
+        fingerprint_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_duplicate_add() {
+        duplicate_add_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_match_as_tcam() {
+        match_as_tcam_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_best_fit() {
+        best_fit_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_covered_addresses() {
+        covered_addresses_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_is_routable() {
+        is_routable_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_has_same_cidrs() {
+        has_same_cidrs_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_find_by_range() {
+        find_by_range_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_aggregated() {
+        aggregated_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_lookup_or_default() {
+        lookup_or_default_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_leaf_pairs() {
+        leaf_pairs_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_routes_in_range() {
+        routes_in_range_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_routes_of_length() {
+        routes_of_length_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_complement_within() {
+        complement_within_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_iter_with_depth() {
+        iter_with_depth_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "fills and scans a 16M-entry table")]
+    fn test_hash_to_fib_24() {
+        to_fib_24_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_into_sorted_vec() {
+        assert_eq!(HashRoutingTable::new().into_sorted_vec(), vec![]);
+
+        let mut routing_table = HashRoutingTable::new();
+        for cidr in unsorted_cidrs() {
+            routing_table.add_cidr(cidr);
+        }
+
+        assert_eq!(routing_table.into_sorted_vec(), sorted_cidrs());
+    }
+
+    #[test]
+    fn test_hash_filter_routable() {
+        filter_routable_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_longest_prefix_match_detailed() {
+        longest_prefix_match_detailed_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_longest_prefix_match_capped() {
+        longest_prefix_match_capped_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_count_within() {
+        count_within_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_leaf_pairs_be() {
+        leaf_pairs_be_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_aggregates_exceeding() {
+        aggregates_exceeding_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_match_with_backup() {
+        match_with_backup_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_cidrs_sorted() {
+        cidrs_sorted_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_cidrs_sorted_is_deterministic() {
+        let mut cidrs: Vec<Ipv4Cidr> = (0..=255u8)
+            .map(|third| {
+                Ipv4Cidr::containing(Ipv4Addr::new(10, third, third, 0), 8 + third % 25).unwrap()
+            })
+            .collect();
+        cidrs.extend(unsorted_cidrs());
+
+        let mut forward = HashRoutingTable::new();
+        let mut backward = HashRoutingTable::new();
+        for &cidr in cidrs.iter() {
+            forward.add_cidr(cidr);
+        }
+        for &cidr in cidrs.iter().rev() {
+            backward.add_cidr(cidr);
+        }
+
+        let expected = forward.cidrs_sorted();
+        for _ in 0..3 {
+            assert_eq!(forward.cidrs_sorted(), expected);
+            assert_eq!(backward.cidrs_sorted(), expected);
+        }
+        assert_eq!(forward.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn test_hash_remove_cidrs() {
+        remove_cidrs_test(HashRoutingTable::new());
+    }
+
+    #[test]
+    fn test_hash_default_free() {
+        default_free_test(Box::new(HashRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_hash_iter_default_route() {
+        let mut routing_table = HashRoutingTable::new();
+        let default_route = Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap();
+        routing_table.add_cidr(default_route);
+
+        assert_eq!(
+            routing_table.iter().collect::<Vec<_>>(),
+            vec![default_route]
+        );
+        assert_eq!(routing_table.size(), routing_table.iter().count());
+        assert_eq!(routing_table.cidrs_sorted(), vec![default_route]);
+        assert_eq!(routing_table.routes_of_length(0), vec![default_route]);
+        assert_eq!(routing_table.leaf_pairs(), vec![(0, 0)]);
+
+        let mut rebuilt = HashRoutingTable::new();
+        for cidr in routing_table.cidrs_sorted() {
+            rebuilt.add_cidr(cidr);
+        }
+        assert!(rebuilt.has_same_cidrs(&routing_table));
+        assert_eq!(
+            rebuilt.find_exact_cidr(Ipv4Addr::new(8, 8, 8, 8)),
+            Some(default_route)
+        );
+        assert_eq!(rebuilt.into_sorted_vec(), vec![default_route]);
+    }
 }
@@ -0,0 +1,267 @@
+use super::{AddrRole, RoutingTable};
+use crate::Ipv4Cidr;
+use std::{cell::Cell, net::Ipv4Addr};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct LookupStats {
+    pub total: u64,
+    pub misses: u64,
+    pub matched_lengths: [u64; 33],
+}
+
+impl Default for LookupStats {
+    fn default() -> Self {
+        Self {
+            total: 0,
+            misses: 0,
+            matched_lengths: [0; 33],
+        }
+    }
+}
+
+// Only `find_exact_cidr` is counted. Every other query is forwarded to the inner table, so
+// lookups made through trait defaults such as `is_routable` leave the stats as is and the
+// inner table keeps its own overrides.
+#[derive(Default)]
+pub struct InstrumentedRoutingTable<T> {
+    inner: T,
+    stats: Cell<LookupStats>,
+}
+
+impl<T: RoutingTable> InstrumentedRoutingTable<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            stats: Cell::new(LookupStats::default()),
+        }
+    }
+
+    pub fn lookup_stats(&self) -> LookupStats {
+        self.stats.get()
+    }
+
+    pub fn reset_stats(&self) {
+        self.stats.set(LookupStats::default());
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: RoutingTable> RoutingTable for InstrumentedRoutingTable<T> {
+    fn add_cidr(&mut self, cidr: Ipv4Cidr) {
+        self.inner.add_cidr(cidr);
+    }
+
+    fn remove_cidr(&mut self, cidr: Ipv4Cidr) {
+        self.inner.remove_cidr(cidr);
+    }
+
+    fn remove_cidrs(&mut self, cidrs: impl IntoIterator<Item = Ipv4Cidr>) -> usize {
+        self.inner.remove_cidrs(cidrs)
+    }
+
+    fn drain_matching(&mut self, f: &mut dyn FnMut(Ipv4Cidr) -> bool) -> Vec<Ipv4Cidr> {
+        self.inner.drain_matching(f)
+    }
+
+    fn remove_within(&mut self, scope: Ipv4Cidr) -> Vec<Ipv4Cidr> {
+        self.inner.remove_within(scope)
+    }
+
+    fn find_exact_cidr(&self, addr: Ipv4Addr) -> Option<Ipv4Cidr> {
+        let result = self.inner.find_exact_cidr(addr);
+
+        let mut stats = self.stats.get();
+        stats.total += 1;
+        match result {
+            Some(cidr) => stats.matched_lengths[cidr.prefix_len() as usize] += 1,
+            None => stats.misses += 1,
+        }
+        self.stats.set(stats);
+
+        result
+    }
+
+    fn match_with_backup(&self, addr: Ipv4Addr) -> Option<(Ipv4Cidr, Option<Ipv4Cidr>)> {
+        self.inner.match_with_backup(addr)
+    }
+
+    fn longest_prefix_match_capped(&self, addr: Ipv4Addr, max_len: u8) -> Option<Ipv4Cidr> {
+        self.inner.longest_prefix_match_capped(addr, max_len)
+    }
+
+    fn longest_prefix_match_detailed(&self, addr: Ipv4Addr) -> Option<(Ipv4Cidr, bool)> {
+        self.inner.longest_prefix_match_detailed(addr)
+    }
+
+    fn lookup_or_default(&self, addr: Ipv4Addr, default: Ipv4Cidr) -> Ipv4Cidr {
+        self.inner.lookup_or_default(addr, default)
+    }
+
+    fn get(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.inner.get(cidr)
+    }
+
+    fn is_routable(&self, addr: Ipv4Addr) -> bool {
+        self.inner.is_routable(addr)
+    }
+
+    fn filter_routable(&self, addrs: &[Ipv4Addr]) -> Vec<bool> {
+        self.inner.filter_routable(addrs)
+    }
+
+    fn classify_address(&self, addr: Ipv4Addr) -> Option<AddrRole> {
+        self.inner.classify_address(addr)
+    }
+
+    fn match_as_tcam(&self, addr: Ipv4Addr) -> Option<(u32, u32)> {
+        self.inner.match_as_tcam(addr)
+    }
+
+    fn find_covering(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.inner.find_covering(cidr)
+    }
+
+    fn find_parent(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.inner.find_parent(cidr)
+    }
+
+    fn match_path(&self, addr: Ipv4Addr) -> Vec<Ipv4Cidr> {
+        self.inner.match_path(addr)
+    }
+
+    fn hierarchy_neighbors(&self, cidr: Ipv4Cidr) -> (Option<Ipv4Cidr>, Vec<Ipv4Cidr>) {
+        self.inner.hierarchy_neighbors(cidr)
+    }
+
+    fn count_within(&self, scope: Ipv4Cidr) -> usize {
+        self.inner.count_within(scope)
+    }
+
+    fn routes_in_range(&self, start: Ipv4Addr, end: Ipv4Addr) -> Vec<Ipv4Cidr> {
+        self.inner.routes_in_range(start, end)
+    }
+
+    fn routes_of_length(&self, len: u8) -> Vec<Ipv4Cidr> {
+        self.inner.routes_of_length(len)
+    }
+
+    fn size(&self) -> usize {
+        self.inner.size()
+    }
+
+    fn approx_heap_bytes(&self) -> usize {
+        self.inner.approx_heap_bytes()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Ipv4Cidr> + '_> {
+        self.inner.iter()
+    }
+
+    fn cidrs_sorted(&self) -> Vec<Ipv4Cidr> {
+        self.inner.cidrs_sorted()
+    }
+
+    fn iter_with_depth(&self) -> Vec<(Ipv4Cidr, usize)> {
+        self.inner.iter_with_depth()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InstrumentedRoutingTable, LookupStats};
+    use crate::{
+        routing_table::tests::{complex_test, simple_test},
+        HashRoutingTable, Ipv4Cidr, RoutingTable, TrieRoutingTable,
+    };
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_instrumented_simple() {
+        simple_test(Box::new(InstrumentedRoutingTable::new(
+            TrieRoutingTable::new(),
+        )));
+    }
+
+    #[test]
+    fn test_instrumented_complex() {
+        complex_test(Box::new(InstrumentedRoutingTable::new(
+            HashRoutingTable::new(),
+        )));
+    }
+
+    #[test]
+    fn test_instrumented_lookup_stats() {
+        let mut routing_table = InstrumentedRoutingTable::new(TrieRoutingTable::new());
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap());
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap());
+
+        assert_eq!(routing_table.lookup_stats(), LookupStats::default());
+
+        let addrs = [
+            Ipv4Addr::new(10, 1, 2, 3),
+            Ipv4Addr::new(10, 1, 200, 3),
+            Ipv4Addr::new(10, 2, 0, 1),
+            Ipv4Addr::new(11, 0, 0, 1),
+            Ipv4Addr::new(192, 168, 0, 1),
+        ];
+        for addr in addrs {
+            routing_table.find_exact_cidr(addr);
+        }
+        routing_table.add_cidr(Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 2, 3)));
+        routing_table.get(Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 2, 3)));
+
+        let mut matched_lengths = [0; 33];
+        matched_lengths[8] = 1;
+        matched_lengths[16] = 2;
+        assert_eq!(
+            routing_table.lookup_stats(),
+            LookupStats {
+                total: 5,
+                misses: 2,
+                matched_lengths,
+            }
+        );
+
+        routing_table.reset_stats();
+        assert_eq!(routing_table.lookup_stats(), LookupStats::default());
+    }
+
+    #[test]
+    fn test_instrumented_other_queries_not_counted() {
+        let mut routing_table = InstrumentedRoutingTable::new(TrieRoutingTable::new());
+        let aggregate = Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap();
+        routing_table.add_cidr(aggregate);
+        let addr = Ipv4Addr::new(10, 1, 2, 3);
+        let miss = Ipv4Addr::new(192, 168, 0, 1);
+
+        assert!(routing_table.is_routable(addr));
+        assert_eq!(routing_table.lookup_or_default(miss, aggregate), aggregate);
+        assert!(routing_table.classify_address(addr).is_some());
+        assert_eq!(
+            routing_table.match_with_backup(addr),
+            Some((aggregate, None))
+        );
+        assert_eq!(
+            routing_table.filter_routable(&[addr, miss]),
+            vec![true, false]
+        );
+        assert_eq!(
+            routing_table.longest_prefix_match_detailed(addr),
+            Some((aggregate, false))
+        );
+        assert!(routing_table.match_as_tcam(addr).is_some());
+        assert_eq!(
+            routing_table.longest_prefix_match_capped(addr, 8),
+            Some(aggregate)
+        );
+        assert_eq!(
+            routing_table.count_within(Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap()),
+            1
+        );
+
+        assert_eq!(routing_table.lookup_stats(), LookupStats::default());
+    }
+}
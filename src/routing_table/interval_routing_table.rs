@@ -0,0 +1,582 @@
+use crate::{Ipv4Cidr, RoutingTable};
+use std::net::Ipv4Addr;
+
+#[derive(Clone, Copy)]
+struct Interval {
+    start: u32,
+    end: u32,
+    len: u8,
+    parent: Option<usize>,
+}
+
+impl Interval {
+    fn new(cidr: Ipv4Cidr) -> Self {
+        Self {
+            start: u32::from(cidr.min()),
+            end: u32::from(cidr.max()),
+            len: cidr.prefix_len(),
+            parent: None,
+        }
+    }
+
+    #[inline]
+    fn contains(&self, bits: u32) -> bool {
+        self.start <= bits && bits <= self.end
+    }
+
+    fn to_cidr(self) -> Ipv4Cidr {
+        Ipv4Cidr::from_bits(self.start, self.len).expect("Len and Ipv4Addr should always be valid.")
+    }
+}
+
+// Lookups are a binary search plus a walk up the parent chain. Single inserts and removals
+// still shift the sorted vector and every parent index behind them, so they are O(n); build
+// large tables with `from_cidrs`.
+#[derive(Default)]
+pub struct IntervalRoutingTable {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalRoutingTable {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    pub fn from_cidrs(cidrs: &[Ipv4Cidr]) -> Self {
+        let mut intervals: Vec<Interval> = cidrs.iter().map(|&cidr| Interval::new(cidr)).collect();
+        intervals.sort_unstable_by_key(|interval| (interval.start, interval.len));
+        intervals.dedup_by_key(|interval| (interval.start, interval.len));
+
+        let mut table = Self { intervals };
+        table.link_parents();
+        table
+    }
+
+    // Intervals are already kept in network-then-length order.
+    pub fn into_sorted_vec(self) -> Vec<Ipv4Cidr> {
+        self.intervals
+            .into_iter()
+            .map(|interval| interval.to_cidr())
+            .collect()
+    }
+
+    fn search(&self, start: u32, len: u8) -> Result<usize, usize> {
+        self.intervals
+            .binary_search_by_key(&(start, len), |interval| (interval.start, interval.len))
+    }
+
+    // Intervals are sorted by start and then by length (widest first), so the enclosing
+    // interval of each entry is always on the stack of still-open intervals before it.
+    fn link_parents(&mut self) {
+        let mut open: Vec<usize> = Vec::with_capacity(33);
+
+        for idx in 0..self.intervals.len() {
+            let start = self.intervals[idx].start;
+            while let Some(&top) = open.last() {
+                if self.intervals[top].contains(start) {
+                    break;
+                }
+                open.pop();
+            }
+
+            self.intervals[idx].parent = open.last().copied();
+            open.push(idx);
+        }
+    }
+
+    // The enclosing interval of the new entry is `idx - 1` or one of its ancestors, and only
+    // the entries inside the new span can have it as their parent.
+    fn link_inserted(&mut self, idx: usize) {
+        for interval in self.intervals.iter_mut() {
+            if let Some(parent) = interval.parent.as_mut().filter(|parent| **parent >= idx) {
+                *parent += 1;
+            }
+        }
+
+        let (start, end) = (self.intervals[idx].start, self.intervals[idx].end);
+        let mut parent = idx.checked_sub(1);
+        while let Some(candidate) = parent {
+            if self.intervals[candidate].contains(start) {
+                break;
+            }
+            parent = self.intervals[candidate].parent;
+        }
+
+        self.intervals[idx].parent = parent;
+        for interval in self.intervals[idx + 1..].iter_mut() {
+            if interval.start > end {
+                break;
+            }
+            if interval.parent == parent {
+                interval.parent = Some(idx);
+            }
+        }
+    }
+
+    // Direct children of the removed entry move up to its parent; everything else only has
+    // its parent index shifted.
+    fn unlink_removed(&mut self, idx: usize) {
+        let removed = self.intervals.remove(idx);
+
+        for interval in self.intervals[idx..].iter_mut() {
+            if interval.start > removed.end {
+                break;
+            }
+            if interval.parent == Some(idx) {
+                interval.parent = removed.parent;
+            }
+        }
+
+        for interval in self.intervals.iter_mut() {
+            if let Some(parent) = interval.parent.as_mut().filter(|parent| **parent > idx) {
+                *parent -= 1;
+            }
+        }
+    }
+
+    // The last interval starting at or before `bits` is either the tightest match itself
+    // or nested inside it, so the match is found by walking up the parent chain.
+    fn find_deepest(&self, bits: u32) -> Option<usize> {
+        let mut current = self
+            .intervals
+            .partition_point(|interval| interval.start <= bits)
+            .checked_sub(1);
+
+        while let Some(idx) = current {
+            let interval = &self.intervals[idx];
+            if interval.contains(bits) {
+                return Some(idx);
+            }
+
+            current = interval.parent;
+        }
+
+        None
+    }
+
+    fn find_longest(&self, bits: u32, max_len: u8) -> Option<Ipv4Cidr> {
+        let mut current = self.find_deepest(bits);
+
+        while let Some(idx) = current {
+            let interval = self.intervals[idx];
+            if interval.len <= max_len {
+                return Some(interval.to_cidr());
+            }
+
+            current = interval.parent;
+        }
+
+        None
+    }
+}
+
+impl RoutingTable for IntervalRoutingTable {
+    fn add_cidr(&mut self, cidr: Ipv4Cidr) {
+        let interval = Interval::new(cidr);
+
+        if let Err(idx) = self.search(interval.start, interval.len) {
+            self.intervals.insert(idx, interval);
+            self.link_inserted(idx);
+        }
+    }
+
+    fn remove_cidr(&mut self, cidr: Ipv4Cidr) {
+        if let Ok(idx) = self.search(u32::from(cidr.min()), cidr.prefix_len()) {
+            self.unlink_removed(idx);
+        }
+    }
+
+    // Parent links are rebuilt once for the whole batch instead of after every removal.
+    fn remove_cidrs(&mut self, cidrs: impl IntoIterator<Item = Ipv4Cidr>) -> usize {
+        let mut targets: Vec<(u32, u8)> = cidrs
+            .into_iter()
+            .map(|cidr| (u32::from(cidr.min()), cidr.prefix_len()))
+            .collect();
+        targets.sort_unstable();
+
+        let before = self.intervals.len();
+        self.intervals.retain(|interval| {
+            targets
+                .binary_search(&(interval.start, interval.len))
+                .is_err()
+        });
+
+        let removed = before - self.intervals.len();
+        if removed > 0 {
+            self.link_parents();
+        }
+
+        removed
+    }
+
+    fn find_exact_cidr(&self, addr: Ipv4Addr) -> Option<Ipv4Cidr> {
+        self.find_deepest(u32::from(addr))
+            .map(|idx| self.intervals[idx].to_cidr())
+    }
+
+    fn get(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.search(u32::from(cidr.min()), cidr.prefix_len())
+            .ok()
+            .map(|idx| self.intervals[idx].to_cidr())
+    }
+
+    fn longest_prefix_match_capped(&self, addr: Ipv4Addr, max_len: u8) -> Option<Ipv4Cidr> {
+        self.find_longest(u32::from(addr), max_len)
+    }
+
+    fn find_covering(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.find_longest(u32::from(cidr.min()), cidr.prefix_len())
+    }
+
+    fn find_parent(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        match cidr.prefix_len() {
+            0 => None,
+            len => self.find_longest(u32::from(cidr.min()), len - 1),
+        }
+    }
+
+    fn match_path(&self, addr: Ipv4Addr) -> Vec<Ipv4Cidr> {
+        let mut path = Vec::new();
+        let mut current = self.find_deepest(u32::from(addr));
+
+        while let Some(idx) = current {
+            let interval = self.intervals[idx];
+            path.push(interval.to_cidr());
+            current = interval.parent;
+        }
+
+        path.reverse();
+        path
+    }
+
+    fn size(&self) -> usize {
+        self.intervals.len()
+    }
+
+    fn approx_heap_bytes(&self) -> usize {
+        self.intervals.capacity() * size_of::<Interval>()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Ipv4Cidr> + '_> {
+        Box::new(self.intervals.iter().map(|interval| interval.to_cidr()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntervalRoutingTable;
+    use crate::{
+        routing_table::tests::{
+            add_cidr_max_len_test, aggregated_test, aggregates_exceeding_test,
+            approx_heap_bytes_test, best_fit_test, cidrs_sorted_test, classify_address_test,
+            complement_within_test, complex_test, count_within_test, coverage_test,
+            covered_addresses_test, covers_test, default_free_test, drain_matching_test,
+            duplicate_add_test, empty_test, filter_routable_test, find_by_range_test,
+            find_parent_test, fingerprint_test, get_test, has_same_cidrs_test,
+            hierarchy_neighbors_test, is_routable_test, iter_with_depth_test, leaf_pairs_be_test,
+            leaf_pairs_test, longest_prefix_match_capped_test, longest_prefix_match_detailed_test,
+            lookup_or_default_test, match_as_tcam_test, match_path_test, match_with_backup_test,
+            next_free_subnet_test, one_global_cidr, remove_cidrs_test, remove_within_test,
+            routes_in_range_test, routes_of_length_test, simple_test, sorted_cidrs, to_fib_24_test,
+            unsorted_cidrs,
+        },
+        Ipv4Cidr, RoutingTable,
+    };
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_interval_empty_case() {
+        empty_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_one_global_cidr() {
+        one_global_cidr(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_simple() {
+        simple_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_complex() {
+        complex_test(Box::new(IntervalRoutingTable::new()))
+    }
+
+    #[test]
+    fn test_interval_find_parent() {
+        find_parent_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_covers() {
+        covers_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_match_path() {
+        match_path_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_from_cidrs() {
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 200, 0, 0), 16).unwrap(),
+        ];
+
+        let routing_table = IntervalRoutingTable::from_cidrs(&cidrs);
+        let test_cases = [
+            (Ipv4Addr::new(10, 1, 2, 3), Some(cidrs[0])),
+            (Ipv4Addr::new(10, 1, 3, 3), Some(cidrs[3])),
+            (Ipv4Addr::new(10, 2, 0, 0), Some(cidrs[1])),
+            (Ipv4Addr::new(10, 255, 0, 0), Some(cidrs[1])),
+            (Ipv4Addr::new(11, 0, 0, 0), None),
+        ];
+
+        for (addr, expected) in test_cases {
+            let result = routing_table.find_exact_cidr(addr);
+
+            assert_eq!(
+                result, expected,
+                "we find {addr} inside manager and expect result to be {expected:?}"
+            );
+        }
+
+        assert_eq!(4, routing_table.size());
+    }
+
+    #[test]
+    fn test_interval_get() {
+        get_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_coverage() {
+        coverage_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_hierarchy_neighbors() {
+        hierarchy_neighbors_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_add_cidr_max_len() {
+        add_cidr_max_len_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_drain_matching() {
+        drain_matching_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_next_free_subnet() {
+        next_free_subnet_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_classify_address() {
+        classify_address_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_remove_within() {
+        remove_within_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_approx_heap_bytes() {
+        approx_heap_bytes_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_fingerprint() {
+        fingerprint_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_duplicate_add() {
+        duplicate_add_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_match_as_tcam() {
+        match_as_tcam_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_best_fit() {
+        best_fit_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_covered_addresses() {
+        covered_addresses_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_is_routable() {
+        is_routable_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_has_same_cidrs() {
+        has_same_cidrs_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_find_by_range() {
+        find_by_range_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_aggregated() {
+        aggregated_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_lookup_or_default() {
+        lookup_or_default_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_leaf_pairs() {
+        leaf_pairs_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_routes_in_range() {
+        routes_in_range_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_routes_of_length() {
+        routes_of_length_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_complement_within() {
+        complement_within_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_iter_with_depth() {
+        iter_with_depth_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "fills and scans a 16M-entry table")]
+    fn test_interval_to_fib_24() {
+        to_fib_24_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_into_sorted_vec() {
+        assert_eq!(IntervalRoutingTable::new().into_sorted_vec(), vec![]);
+
+        let mut routing_table = IntervalRoutingTable::new();
+        for cidr in unsorted_cidrs() {
+            routing_table.add_cidr(cidr);
+        }
+
+        assert_eq!(routing_table.into_sorted_vec(), sorted_cidrs());
+    }
+
+    #[test]
+    fn test_interval_filter_routable() {
+        filter_routable_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_longest_prefix_match_detailed() {
+        longest_prefix_match_detailed_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_longest_prefix_match_capped() {
+        longest_prefix_match_capped_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_count_within() {
+        count_within_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_leaf_pairs_be() {
+        leaf_pairs_be_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_aggregates_exceeding() {
+        aggregates_exceeding_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_match_with_backup() {
+        match_with_backup_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_cidrs_sorted() {
+        cidrs_sorted_test(Box::new(IntervalRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_interval_incremental_links_match_rebuild() {
+        let mut seed = 0x2545_f491u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            seed
+        };
+
+        let mut routing_table = IntervalRoutingTable::new();
+        let steps = if cfg!(miri) { 200 } else { 2_000 };
+        for step in 0..steps {
+            let bits = next();
+            // Few distinct networks and lengths, so entries nest and repeat often.
+            let cidr = Ipv4Cidr::containing(Ipv4Addr::from(bits & 0xff0f_0000), (bits % 17) as u8)
+                .unwrap();
+
+            if step % 3 == 0 {
+                routing_table.remove_cidr(cidr);
+            } else {
+                routing_table.add_cidr(cidr);
+            }
+
+            let parents: Vec<Option<usize>> = routing_table
+                .intervals
+                .iter()
+                .map(|interval| interval.parent)
+                .collect();
+            routing_table.link_parents();
+            let rebuilt: Vec<Option<usize>> = routing_table
+                .intervals
+                .iter()
+                .map(|interval| interval.parent)
+                .collect();
+            assert_eq!(
+                parents, rebuilt,
+                "we expect step {step} ({cidr}) to keep links intact"
+            );
+        }
+    }
+
+    #[test]
+    fn test_interval_remove_cidrs() {
+        remove_cidrs_test(IntervalRoutingTable::new());
+    }
+
+    #[test]
+    fn test_interval_default_free() {
+        default_free_test(Box::new(IntervalRoutingTable::new()));
+    }
+}
@@ -10,23 +10,28 @@ impl ListRoutingTable {
     pub fn new() -> Self {
         Self { cidrs: Vec::new() }
     }
-}
 
-impl RoutingTable for ListRoutingTable {
-    fn add_cidr(&mut self, cidr: Ipv4Cidr) {
-        self.cidrs.push(cidr);
+    pub fn into_sorted_vec(mut self) -> Vec<Ipv4Cidr> {
+        self.cidrs
+            .sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+        self.cidrs
     }
 
-    fn remove_cidr(&mut self, cidr: Ipv4Cidr) {
-        self.cidrs.retain(|cur| cur != &cidr);
+    // Borrows the stored entry instead of copying it out.
+    pub fn find_ref(&self, addr: Ipv4Addr) -> Option<&Ipv4Cidr> {
+        self.find_longest_ref(addr, 32)
     }
 
-    fn find_exact_cidr(&self, addr: Ipv4Addr) -> Option<Ipv4Cidr> {
+    fn find_longest(&self, addr: Ipv4Addr, max_len: u8) -> Option<Ipv4Cidr> {
+        self.find_longest_ref(addr, max_len).copied()
+    }
+
+    fn find_longest_ref(&self, addr: Ipv4Addr, max_len: u8) -> Option<&Ipv4Cidr> {
         self.cidrs.iter().fold(None, |acc, cidr| {
-            if cidr.contains(addr) {
+            if cidr.prefix_len() <= max_len && cidr.contains(addr) {
                 match acc {
-                    None => Some(*cidr),
-                    Some(other) if other.prefix_len() < cidr.prefix_len() => Some(*cidr),
+                    None => Some(cidr),
+                    Some(other) if other.prefix_len() < cidr.prefix_len() => Some(cidr),
                     Some(_) => acc,
                 }
             } else {
@@ -34,16 +39,87 @@ impl RoutingTable for ListRoutingTable {
             }
         })
     }
+}
+
+impl RoutingTable for ListRoutingTable {
+    fn add_cidr(&mut self, cidr: Ipv4Cidr) {
+        if !self.cidrs.contains(&cidr) {
+            self.cidrs.push(cidr);
+        }
+    }
+
+    fn remove_cidr(&mut self, cidr: Ipv4Cidr) {
+        if let Some(idx) = self.cidrs.iter().position(|cur| cur == &cidr) {
+            self.cidrs.swap_remove(idx);
+        }
+    }
+
+    fn find_exact_cidr(&self, addr: Ipv4Addr) -> Option<Ipv4Cidr> {
+        self.find_longest(addr, 32)
+    }
+
+    fn get(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.cidrs.iter().find(|&&cur| cur == cidr).copied()
+    }
+
+    fn longest_prefix_match_capped(&self, addr: Ipv4Addr, max_len: u8) -> Option<Ipv4Cidr> {
+        self.find_longest(addr, max_len)
+    }
+
+    fn find_covering(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.find_longest(cidr.min(), cidr.prefix_len())
+    }
+
+    fn find_parent(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        match cidr.prefix_len() {
+            0 => None,
+            len => self.find_longest(cidr.min(), len - 1),
+        }
+    }
+
+    fn match_path(&self, addr: Ipv4Addr) -> Vec<Ipv4Cidr> {
+        let mut path: Vec<Ipv4Cidr> = self
+            .cidrs
+            .iter()
+            .filter(|cidr| cidr.contains(addr))
+            .copied()
+            .collect();
+
+        path.sort_unstable_by_key(|cidr| cidr.prefix_len());
+        path
+    }
 
     fn size(&self) -> usize {
         self.cidrs.len()
     }
+
+    fn approx_heap_bytes(&self) -> usize {
+        self.cidrs.capacity() * size_of::<Ipv4Cidr>()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Ipv4Cidr> + '_> {
+        Box::new(self.cidrs.iter().copied())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::ListRoutingTable;
-    use crate::routing_table::tests::{complex_test, empty_test, simple_test, one_global_cidr};
+    use crate::routing_table::tests::{
+        add_cidr_max_len_test, aggregated_test, aggregates_exceeding_test, approx_heap_bytes_test,
+        best_fit_test, cidrs_sorted_test, classify_address_test, complement_within_test,
+        complex_test, count_within_test, coverage_test, covered_addresses_test, covers_test,
+        default_free_test, drain_matching_test, duplicate_add_test, empty_test,
+        filter_routable_test, find_by_range_test, find_parent_test, fingerprint_test, get_test,
+        has_same_cidrs_test, hierarchy_neighbors_test, is_routable_test, iter_with_depth_test,
+        leaf_pairs_be_test, leaf_pairs_test, longest_prefix_match_capped_test,
+        longest_prefix_match_detailed_test, lookup_or_default_test, match_as_tcam_test,
+        match_path_test, match_with_backup_test, next_free_subnet_test, one_global_cidr,
+        remove_cidrs_test, remove_within_test, routes_in_range_test, routes_of_length_test,
+        simple_test, sorted_cidrs, to_fib_24_test, unsorted_cidrs,
+    };
+    use crate::{Ipv4Cidr, RoutingTable};
+    use std::{net::Ipv4Addr, ptr};
 
     #[test]
     fn test_list_empty_case() {
@@ -64,4 +140,229 @@ mod tests {
     fn test_list_complex() {
         complex_test(Box::new(ListRoutingTable::new()))
     }
+
+    #[test]
+    fn test_list_find_parent() {
+        find_parent_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_covers() {
+        covers_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_match_path() {
+        match_path_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_get() {
+        get_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_coverage() {
+        coverage_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_hierarchy_neighbors() {
+        hierarchy_neighbors_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_add_cidr_max_len() {
+        add_cidr_max_len_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_drain_matching() {
+        drain_matching_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_next_free_subnet() {
+        next_free_subnet_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_classify_address() {
+        classify_address_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_remove_within() {
+        remove_within_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_approx_heap_bytes() {
+        approx_heap_bytes_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_fingerprint() {
+        fingerprint_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_duplicate_add() {
+        duplicate_add_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_match_as_tcam() {
+        match_as_tcam_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_best_fit() {
+        best_fit_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_covered_addresses() {
+        covered_addresses_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_is_routable() {
+        is_routable_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_has_same_cidrs() {
+        has_same_cidrs_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_find_by_range() {
+        find_by_range_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_aggregated() {
+        aggregated_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_lookup_or_default() {
+        lookup_or_default_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_leaf_pairs() {
+        leaf_pairs_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_routes_in_range() {
+        routes_in_range_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_routes_of_length() {
+        routes_of_length_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_complement_within() {
+        complement_within_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_iter_with_depth() {
+        iter_with_depth_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "fills and scans a 16M-entry table")]
+    fn test_list_to_fib_24() {
+        to_fib_24_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_into_sorted_vec() {
+        assert_eq!(ListRoutingTable::new().into_sorted_vec(), vec![]);
+
+        let mut routing_table = ListRoutingTable::new();
+        for cidr in unsorted_cidrs() {
+            routing_table.add_cidr(cidr);
+        }
+
+        assert_eq!(routing_table.into_sorted_vec(), sorted_cidrs());
+    }
+
+    #[test]
+    fn test_list_filter_routable() {
+        filter_routable_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_longest_prefix_match_detailed() {
+        longest_prefix_match_detailed_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_longest_prefix_match_capped() {
+        longest_prefix_match_capped_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_count_within() {
+        count_within_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_leaf_pairs_be() {
+        leaf_pairs_be_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_aggregates_exceeding() {
+        aggregates_exceeding_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_match_with_backup() {
+        match_with_backup_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_cidrs_sorted() {
+        cidrs_sorted_test(Box::new(ListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_list_find_ref() {
+        let mut routing_table = ListRoutingTable::new();
+        assert_eq!(routing_table.find_ref(Ipv4Addr::new(10, 1, 2, 3)), None);
+
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap(),
+        ];
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        let found = routing_table.find_ref(Ipv4Addr::new(10, 1, 2, 3)).unwrap();
+        assert_eq!(*found, cidrs[1]);
+        assert!(ptr::eq(found, &routing_table.cidrs[1]));
+
+        let found = routing_table.find_ref(Ipv4Addr::new(10, 2, 0, 0)).unwrap();
+        assert!(ptr::eq(found, &routing_table.cidrs[0]));
+        assert_eq!(routing_table.find_ref(Ipv4Addr::new(11, 0, 0, 0)), None);
+    }
+
+    #[test]
+    fn test_list_remove_cidrs() {
+        remove_cidrs_test(ListRoutingTable::new());
+    }
+
+    #[test]
+    fn test_list_default_free() {
+        default_free_test(Box::new(ListRoutingTable::new()));
+    }
 }
@@ -1,27 +1,552 @@
-use crate::Ipv4Cidr;
+use crate::{
+    aggregation::{aggregate, range_to_cidrs},
+    errors::NetworkParseError,
+    utils::{get_cidr_mask, merge_ranges},
+    Ipv4Cidr,
+};
 pub use hash_routing_table::HashRoutingTable;
+pub use instrumented_routing_table::{InstrumentedRoutingTable, LookupStats};
+pub use interval_routing_table::IntervalRoutingTable;
 pub use list_routing_table::ListRoutingTable;
-use std::net::Ipv4Addr;
+pub use observable_routing_table::{ObservableRoutingTable, RouteEvent};
+pub use priority_list_routing_table::PriorityListRoutingTable;
+pub use ref_counted_routing_table::RefCountedRoutingTable;
+pub use routing_table_map::RoutingTableMap;
+use std::{collections::BTreeMap, net::Ipv4Addr};
 pub use trie_routing_table::TrieRoutingTable;
 
 mod hash_routing_table;
+mod instrumented_routing_table;
+mod interval_routing_table;
 mod list_routing_table;
+mod observable_routing_table;
+mod priority_list_routing_table;
+mod ref_counted_routing_table;
+mod routing_table_map;
 mod trie_routing_table;
 
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum AddrRole {
+    Network,
+    Broadcast,
+    Host,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum TableKind {
+    List,
+    Hash,
+    Trie,
+}
+
+const AUTO_LIST_THRESHOLD: usize = 64;
+const AUTO_SKEW_PERCENT: usize = 90;
+
+// Markers in the first-level table built by `RoutingTable::to_fib_24`.
+pub const FIB_NO_ROUTE: u16 = u16::MAX;
+pub const FIB_DESCEND: u16 = u16::MAX - 1;
+
+/// Picks a table for `cidrs` and loads them into it:
+/// - fewer than 64 cidrs: [`ListRoutingTable`], a linear scan is cheapest at that size;
+/// - at least 90% of cidrs sharing one prefix length: [`HashRoutingTable`];
+/// - anything else: [`TrieRoutingTable`].
+pub fn build_auto(cidrs: &[Ipv4Cidr]) -> Box<dyn RoutingTable> {
+    let mut routing_table: Box<dyn RoutingTable> = if cidrs.len() < AUTO_LIST_THRESHOLD {
+        Box::new(ListRoutingTable::new())
+    } else {
+        let mut lengths = [0usize; 33];
+        for cidr in cidrs {
+            lengths[cidr.prefix_len() as usize] += 1;
+        }
+
+        let dominant = lengths.iter().max().copied().unwrap_or(0);
+        if dominant * 100 >= cidrs.len() * AUTO_SKEW_PERCENT {
+            Box::new(HashRoutingTable::new())
+        } else {
+            Box::new(TrieRoutingTable::new())
+        }
+    };
+
+    for &cidr in cidrs {
+        routing_table.add_cidr(cidr);
+    }
+
+    routing_table
+}
+
+pub fn new_table(kind: TableKind) -> Box<dyn RoutingTable> {
+    match kind {
+        TableKind::List => Box::new(ListRoutingTable::new()),
+        TableKind::Hash => Box::new(HashRoutingTable::new()),
+        TableKind::Trie => Box::new(TrieRoutingTable::new()),
+    }
+}
+
+pub fn union<T: RoutingTable + Default>(a: &dyn RoutingTable, b: &dyn RoutingTable) -> T {
+    let mut routing_table = T::default();
+    for cidr in a.iter().chain(b.iter()) {
+        routing_table.add_cidr(cidr);
+    }
+
+    routing_table
+}
+
+pub fn intersection<T: RoutingTable + Default>(a: &dyn RoutingTable, b: &dyn RoutingTable) -> T {
+    let mut routing_table = T::default();
+    for cidr in a.iter().filter(|&cidr| b.get(cidr).is_some()) {
+        routing_table.add_cidr(cidr);
+    }
+
+    routing_table
+}
+
+pub fn difference<T: RoutingTable + Default>(a: &dyn RoutingTable, b: &dyn RoutingTable) -> T {
+    let mut routing_table = T::default();
+    for cidr in a.iter().filter(|&cidr| b.get(cidr).is_none()) {
+        routing_table.add_cidr(cidr);
+    }
+
+    routing_table
+}
+
 pub trait RoutingTable {
     fn add_cidr(&mut self, cidr: Ipv4Cidr);
 
+    fn add_cidr_max_len(&mut self, cidr: Ipv4Cidr, max_len: u8) -> Result<(), NetworkParseError> {
+        if cidr.prefix_len() > max_len {
+            return Err(NetworkParseError::PrefixTooLong);
+        }
+
+        self.add_cidr(cidr);
+        Ok(())
+    }
+
     fn remove_cidr(&mut self, cidr: Ipv4Cidr);
 
+    // Returns how many of `cidrs` were stored; absent ones are skipped.
+    fn remove_cidrs(&mut self, cidrs: impl IntoIterator<Item = Ipv4Cidr>) -> usize
+    where
+        Self: Sized,
+    {
+        let mut removed = 0;
+        for cidr in cidrs {
+            if self.get(cidr).is_some() {
+                self.remove_cidr(cidr);
+                removed += 1;
+            }
+        }
+
+        removed
+    }
+
+    fn drain_matching(&mut self, f: &mut dyn FnMut(Ipv4Cidr) -> bool) -> Vec<Ipv4Cidr> {
+        let drained: Vec<Ipv4Cidr> = self.iter().filter(|&cidr| f(cidr)).collect();
+
+        for &cidr in &drained {
+            self.remove_cidr(cidr);
+        }
+
+        drained
+    }
+
+    fn remove_within(&mut self, scope: Ipv4Cidr) -> Vec<Ipv4Cidr> {
+        self.drain_matching(&mut |cidr| scope.contains_cidr(&cidr))
+    }
+
     fn find_exact_cidr(&self, addr: Ipv4Addr) -> Option<Ipv4Cidr>;
 
+    // The backup is the matched route's sibling block, when that is stored too.
+    fn match_with_backup(&self, addr: Ipv4Addr) -> Option<(Ipv4Cidr, Option<Ipv4Cidr>)> {
+        let cidr = self.find_exact_cidr(addr)?;
+        let backup = cidr.sibling().and_then(|sibling| self.get(sibling));
+
+        Some((cidr, backup))
+    }
+
+    // Routes longer than `max_len` are skipped as if they were not stored.
+    fn longest_prefix_match_capped(&self, addr: Ipv4Addr, max_len: u8) -> Option<Ipv4Cidr> {
+        self.match_path(addr)
+            .into_iter()
+            .rev()
+            .find(|cidr| cidr.prefix_len() <= max_len)
+    }
+
+    // A /32 can only contain the queried address itself, so `true` marks a host route hit.
+    fn longest_prefix_match_detailed(&self, addr: Ipv4Addr) -> Option<(Ipv4Cidr, bool)> {
+        self.find_exact_cidr(addr)
+            .map(|cidr| (cidr, cidr.is_host()))
+    }
+
+    fn lookup_or_default(&self, addr: Ipv4Addr, default: Ipv4Cidr) -> Ipv4Cidr {
+        self.find_exact_cidr(addr).unwrap_or(default)
+    }
+
+    fn get(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr>;
+
+    fn find_by_range(&self, min: Ipv4Addr, max: Ipv4Addr) -> Option<Ipv4Cidr> {
+        let start = u64::from(u32::from(min));
+        let end = u64::from(u32::from(max));
+        let size = end.checked_sub(start)? + 1;
+
+        if !size.is_power_of_two() || start % size != 0 {
+            return None;
+        }
+
+        let len = 32 - size.trailing_zeros() as u8;
+        self.get(Ipv4Cidr::new(min, len).ok()?)
+    }
+
+    fn is_routable(&self, addr: Ipv4Addr) -> bool {
+        self.find_exact_cidr(addr).is_some()
+    }
+
+    fn filter_routable(&self, addrs: &[Ipv4Addr]) -> Vec<bool> {
+        addrs.iter().map(|&addr| self.is_routable(addr)).collect()
+    }
+
+    fn classify_address(&self, addr: Ipv4Addr) -> Option<AddrRole> {
+        let cidr = self.find_exact_cidr(addr)?;
+
+        let role = if cidr.prefix_len() >= 31 {
+            AddrRole::Host
+        } else if addr == cidr.network() {
+            AddrRole::Network
+        } else if addr == cidr.max() {
+            AddrRole::Broadcast
+        } else {
+            AddrRole::Host
+        };
+
+        Some(role)
+    }
+
+    fn match_as_tcam(&self, addr: Ipv4Addr) -> Option<(u32, u32)> {
+        self.find_exact_cidr(addr).map(|cidr| {
+            let mask = get_cidr_mask(cidr.prefix_len()).expect("Len should always be valid.");
+            (u32::from(cidr.network()), mask)
+        })
+    }
+
+    fn find_covering(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr>;
+
+    fn find_parent(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr>;
+
+    fn match_path(&self, addr: Ipv4Addr) -> Vec<Ipv4Cidr>;
+
+    fn hierarchy_neighbors(&self, cidr: Ipv4Cidr) -> (Option<Ipv4Cidr>, Vec<Ipv4Cidr>) {
+        let mut descendants: Vec<Ipv4Cidr> = self
+            .iter()
+            .filter(|other| cidr.contains_cidr(other) && *other != cidr)
+            .collect();
+        descendants.sort_unstable_by_key(|other| (other.min(), other.prefix_len()));
+
+        let mut children: Vec<Ipv4Cidr> = Vec::new();
+        for descendant in descendants {
+            if !children
+                .last()
+                .is_some_and(|child| child.contains_cidr(&descendant))
+            {
+                children.push(descendant);
+            }
+        }
+
+        (self.find_parent(cidr), children)
+    }
+
+    // Counts stored cidrs strictly inside `scope`; `scope` itself is not included.
+    fn count_within(&self, scope: Ipv4Cidr) -> usize {
+        self.iter()
+            .filter(|cidr| *cidr != scope && scope.contains_cidr(cidr))
+            .count()
+    }
+
+    // Counts follow `count_within`: a stored route equal to the block is not one of its
+    // children. Sorted by block address.
+    fn aggregates_exceeding(
+        &self,
+        aggregate_len: u8,
+        max_children: usize,
+    ) -> Vec<(Ipv4Cidr, usize)> {
+        let Ok(mask) = get_cidr_mask(aggregate_len) else {
+            return Vec::new();
+        };
+
+        let mut children: BTreeMap<u32, usize> = BTreeMap::new();
+        for cidr in self.iter().filter(|cidr| cidr.prefix_len() > aggregate_len) {
+            *children.entry(u32::from(cidr.min()) & mask).or_default() += 1;
+        }
+
+        children
+            .into_iter()
+            .filter(|&(_, count)| count > max_children)
+            .map(|(bits, count)| {
+                let block =
+                    Ipv4Cidr::from_bits(bits, aggregate_len).expect("Len should always be valid.");
+                (block, count)
+            })
+            .collect()
+    }
+
+    fn next_free_subnet(&self, parent: Ipv4Cidr, len: u8) -> Option<Ipv4Cidr> {
+        parent
+            .subnets(len)
+            .find(|&subnet| self.find_covering(subnet).is_none() && self.count_within(subnet) == 0)
+    }
+
+    // Ties between equally sized blocks go to the lowest network address.
+    fn best_fit(&self, min_addresses: u64) -> Option<Ipv4Cidr> {
+        self.iter()
+            .filter(|cidr| cidr.num_addresses() >= min_addresses)
+            .min_by_key(|cidr| (cidr.num_addresses(), cidr.min()))
+    }
+
+    // Sorted by network address and then length; an empty range (`start > end`) matches nothing.
+    fn routes_in_range(&self, start: Ipv4Addr, end: Ipv4Addr) -> Vec<Ipv4Cidr> {
+        if start > end {
+            return Vec::new();
+        }
+
+        let mut routes: Vec<Ipv4Cidr> = self
+            .iter()
+            .filter(|cidr| cidr.min() <= end && start <= cidr.max())
+            .collect();
+
+        routes.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+        routes
+    }
+
+    // Sorted by network address.
+    fn routes_of_length(&self, len: u8) -> Vec<Ipv4Cidr> {
+        let mut routes: Vec<Ipv4Cidr> = self
+            .iter()
+            .filter(|cidr| cidr.prefix_len() == len)
+            .collect();
+
+        routes.sort_unstable_by_key(|cidr| cidr.min());
+        routes
+    }
+
     fn size(&self) -> usize;
+
+    fn approx_heap_bytes(&self) -> usize;
+
+    // The order is unspecified and may change between runs (see `HashRoutingTable`); use
+    // `cidrs_sorted` wherever the output has to be reproducible.
+    fn iter(&self) -> Box<dyn Iterator<Item = Ipv4Cidr> + '_>;
+
+    // Sorted by network address and then by length, identical for every table holding the
+    // same cidrs.
+    fn cidrs_sorted(&self) -> Vec<Ipv4Cidr> {
+        let mut cidrs: Vec<Ipv4Cidr> = self.iter().collect();
+        cidrs.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+        cidrs
+    }
+
+    fn coverage(&self) -> u64 {
+        merge_ranges(self.iter().map(|cidr| (cidr.min(), cidr.max())).collect())
+            .into_iter()
+            .map(|(start, end)| u64::from(u32::from(end)) - u64::from(u32::from(start)) + 1)
+            .sum()
+    }
+
+    fn has_default_route(&self) -> bool {
+        self.get(Ipv4Cidr::from_bits(0, 0).expect("Default route should always be valid."))
+            .is_some()
+    }
+
+    fn is_default_free(&self) -> bool {
+        !self.has_default_route()
+    }
+
+    // Whether the stored routes other than `0.0.0.0/0` alone reach every address.
+    fn is_fully_covered_without_default(&self) -> bool {
+        let ranges = merge_ranges(
+            self.iter()
+                .filter(|cidr| cidr.prefix_len() > 0)
+                .map(|cidr| (cidr.min(), cidr.max()))
+                .collect(),
+        );
+
+        ranges == [(Ipv4Addr::new(0, 0, 0, 0), Ipv4Addr::new(255, 255, 255, 255))]
+    }
+
+    fn coverage_ratio(&self) -> f64 {
+        self.coverage() as f64 / (u32::MAX as f64 + 1.0)
+    }
+
+    /// Yields every covered address exactly once, in ascending order. The output is as large
+    /// as `coverage()`, so a table holding `0.0.0.0/0` enumerates all 2^32 addresses.
+    fn covered_addresses(&self) -> Box<dyn Iterator<Item = Ipv4Addr> + '_> {
+        let ranges = merge_ranges(self.iter().map(|cidr| (cidr.min(), cidr.max())).collect());
+
+        Box::new(
+            ranges
+                .into_iter()
+                .flat_map(|(start, end)| (u32::from(start)..=u32::from(end)).map(Ipv4Addr::from)),
+        )
+    }
+
+    // Depth counts the stored strict ancestors of each cidr. Sorted by network address and
+    // then by length, so every cidr follows the ancestors it is indented under.
+    fn iter_with_depth(&self) -> Vec<(Ipv4Cidr, usize)> {
+        let mut cidrs: Vec<Ipv4Cidr> = self.iter().collect();
+        cidrs.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+
+        let mut open: Vec<Ipv4Cidr> = Vec::with_capacity(33);
+        cidrs
+            .into_iter()
+            .map(|cidr| {
+                while open.last().is_some_and(|top| !top.contains_cidr(&cidr)) {
+                    open.pop();
+                }
+
+                let depth = open.len();
+                open.push(cidr);
+                (cidr, depth)
+            })
+            .collect()
+    }
+
+    /// DIR-24-8 first level: entry `i` covers the /24 block starting at `i << 8`. It holds the
+    /// index into `leaf_pairs()` of the longest route spanning the whole block, [`FIB_DESCEND`]
+    /// when a longer route starts inside it, or [`FIB_NO_ROUTE`].
+    ///
+    /// Panics if the table holds more routes than fit below the markers.
+    fn to_fib_24(&self) -> Box<[u16]> {
+        let pairs = self.leaf_pairs();
+        assert!(
+            pairs.len() <= FIB_DESCEND as usize,
+            "{} routes do not fit in 16-bit fib entries",
+            pairs.len()
+        );
+
+        let mut order: Vec<usize> = (0..pairs.len()).collect();
+        order.sort_by_key(|&idx| pairs[idx].1);
+
+        let mut fib = vec![FIB_NO_ROUTE; 1 << 24].into_boxed_slice();
+        for idx in order {
+            let (bits, len) = pairs[idx];
+            let block = (bits >> 8) as usize;
+
+            if len > 24 {
+                fib[block] = FIB_DESCEND;
+            } else {
+                let blocks = 1usize << (24 - len);
+                fib[block..block + blocks].fill(idx as u16);
+            }
+        }
+
+        fib
+    }
+
+    // Sorted by network bits and then length, so equal tables export identical payloads.
+    fn leaf_pairs(&self) -> Vec<(u32, u8)> {
+        let mut pairs: Vec<(u32, u8)> = self
+            .iter()
+            .map(|cidr| (u32::from(cidr.min()), cidr.prefix_len()))
+            .collect();
+
+        pairs.sort_unstable();
+        pairs
+    }
+
+    // Same order as `leaf_pairs`, with the network in wire (big-endian) byte order.
+    fn leaf_pairs_be(&self) -> Vec<([u8; 4], u8)> {
+        self.leaf_pairs()
+            .into_iter()
+            .map(|(bits, len)| (bits.to_be_bytes(), len))
+            .collect()
+    }
+
+    fn aggregated(&self) -> Vec<Ipv4Cidr> {
+        aggregate(&self.iter().collect::<Vec<_>>())
+    }
+
+    // Everything inside `scope` that no stored cidr reaches, as aligned blocks in
+    // ascending order.
+    fn complement_within(&self, scope: Ipv4Cidr) -> Vec<Ipv4Cidr> {
+        let (scope_start, scope_end) = (u32::from(scope.min()), u32::from(scope.max()));
+        let mut uncovered = Vec::new();
+        let mut cursor = u64::from(scope_start);
+
+        let ranges = merge_ranges(self.iter().map(|cidr| (cidr.min(), cidr.max())).collect());
+        for (start, end) in ranges {
+            let (start, end) = (u64::from(u32::from(start)), u64::from(u32::from(end)));
+            if start > u64::from(scope_end) {
+                break;
+            }
+            if end < cursor {
+                continue;
+            }
+
+            if start > cursor {
+                uncovered.extend(range_to_cidrs(
+                    Ipv4Addr::from(cursor as u32),
+                    Ipv4Addr::from((start - 1) as u32),
+                ));
+            }
+            cursor = end + 1;
+        }
+
+        if cursor <= u64::from(scope_end) {
+            uncovered.extend(range_to_cidrs(Ipv4Addr::from(cursor as u32), scope.max()));
+        }
+
+        uncovered
+    }
+
+    fn covers(&self, other: &dyn RoutingTable) -> bool {
+        let own = merge_ranges(self.iter().map(|cidr| (cidr.min(), cidr.max())).collect());
+        let others = merge_ranges(other.iter().map(|cidr| (cidr.min(), cidr.max())).collect());
+
+        others.into_iter().all(|(start, end)| {
+            let idx = own.partition_point(|&(_, own_end)| own_end < start);
+            own.get(idx)
+                .is_some_and(|&(own_start, own_end)| own_start <= start && end <= own_end)
+        })
+    }
+
+    fn has_same_cidrs(&self, other: &dyn RoutingTable) -> bool {
+        if self.size() != other.size() {
+            return false;
+        }
+
+        let mut own: Vec<Ipv4Cidr> = self.iter().collect();
+        let mut others: Vec<Ipv4Cidr> = other.iter().collect();
+        own.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+        others.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+
+        own == others
+    }
+
+    // Summing per-cidr hashes keeps the result independent of iteration order, so every
+    // implementation holding the same cidrs agrees on it.
+    fn fingerprint(&self) -> u64 {
+        self.iter()
+            .fold(0, |acc, cidr| acc.wrapping_add(fingerprint_hash(cidr)))
+    }
+}
+
+// Packs the network bits and the length into one key and runs it through the splitmix64
+// finalizer. Both steps are bijective, so distinct cidrs never share a hash, even when they
+// share a network address.
+fn fingerprint_hash(cidr: Ipv4Cidr) -> u64 {
+    let mut hash = (u64::from(u32::from(cidr.min())) << 8) | u64::from(cidr.prefix_len());
+
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^ (hash >> 31)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::RoutingTable;
-    use crate::Ipv4Cidr;
+    use super::{
+        build_auto, difference, intersection, new_table, union, AddrRole, RoutingTable, TableKind,
+        FIB_DESCEND, FIB_NO_ROUTE,
+    };
+    use crate::{
+        errors::NetworkParseError, HashRoutingTable, Ipv4Cidr, ListRoutingTable, TrieRoutingTable,
+    };
     use std::net::Ipv4Addr;
 
     pub fn empty_test(routing_table: Box<dyn RoutingTable>) {
@@ -150,4 +675,1410 @@ mod tests {
 
         assert_eq!(cidrs.len(), routing_table.size());
     }
+
+    pub fn find_parent_test(mut routing_table: Box<dyn RoutingTable>) {
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 16), 28).unwrap(),
+        ];
+
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        let test_cases = [
+            (cidrs[3], Some(cidrs[3]), Some(cidrs[2])),
+            (cidrs[2], Some(cidrs[2]), Some(cidrs[1])),
+            (cidrs[0], Some(cidrs[0]), None),
+            (
+                Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 32), 28).unwrap(),
+                Some(cidrs[2]),
+                Some(cidrs[2]),
+            ),
+            (
+                Ipv4Cidr::new(Ipv4Addr::new(10, 2, 0, 0), 16).unwrap(),
+                Some(cidrs[0]),
+                Some(cidrs[0]),
+            ),
+            (
+                Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 7).unwrap(),
+                None,
+                None,
+            ),
+            (
+                Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 28).unwrap(),
+                None,
+                None,
+            ),
+        ];
+
+        for (cidr, covering, parent) in test_cases {
+            assert_eq!(
+                routing_table.find_covering(cidr),
+                covering,
+                "we expect covering cidr of {cidr:?} to be {covering:?}"
+            );
+            assert_eq!(
+                routing_table.find_parent(cidr),
+                parent,
+                "we expect parent cidr of {cidr:?} to be {parent:?}"
+            );
+        }
+    }
+
+    pub fn covers_test(mut routing_table: Box<dyn RoutingTable>) {
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 25).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 128), 25).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 1, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 1, 0), 24).unwrap(),
+        ];
+
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        let mut equivalent = ListRoutingTable::new();
+        equivalent.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 23).unwrap());
+        equivalent.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap());
+
+        assert!(
+            routing_table.covers(&equivalent),
+            "we expect table to cover equivalent table"
+        );
+        assert!(
+            equivalent.covers(routing_table.as_ref()),
+            "we expect equivalent table to cover table"
+        );
+
+        let mut smaller = ListRoutingTable::new();
+        smaller.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 64), 26).unwrap());
+        smaller.add_cidr(Ipv4Cidr::new_host(Ipv4Addr::new(192, 168, 200, 1)));
+
+        assert!(
+            routing_table.covers(&smaller),
+            "we expect table to cover smaller table"
+        );
+        assert!(
+            !smaller.covers(routing_table.as_ref()),
+            "we expect smaller table not to cover table"
+        );
+
+        let mut wider = ListRoutingTable::new();
+        wider.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 22).unwrap());
+
+        assert!(
+            !routing_table.covers(&wider),
+            "we expect table not to cover wider table"
+        );
+        assert!(
+            routing_table.covers(&ListRoutingTable::new()),
+            "we expect every table to cover empty table"
+        );
+    }
+
+    pub fn match_path_test(mut routing_table: Box<dyn RoutingTable>) {
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 3, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap(),
+        ];
+
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        let test_cases = [
+            (
+                Ipv4Addr::new(10, 1, 2, 3),
+                vec![cidrs[1], cidrs[3], cidrs[0]],
+            ),
+            (Ipv4Addr::new(10, 1, 4, 3), vec![cidrs[1], cidrs[3]]),
+            (Ipv4Addr::new(10, 2, 2, 3), vec![cidrs[1]]),
+            (Ipv4Addr::new(11, 1, 2, 3), vec![]),
+        ];
+
+        for (addr, expected) in test_cases {
+            let result = routing_table.match_path(addr);
+
+            assert_eq!(
+                result, expected,
+                "we expect match path of {addr} to be {expected:?}"
+            );
+        }
+    }
+
+    pub fn get_test(mut routing_table: Box<dyn RoutingTable>) {
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 0, 1)),
+        ];
+
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        for cidr in cidrs {
+            assert_eq!(
+                routing_table.get(cidr),
+                Some(cidr),
+                "we expect {cidr:?} to be stored"
+            );
+        }
+
+        let absent = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 9).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 31).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 0, 2)),
+        ];
+
+        for cidr in absent {
+            assert_eq!(
+                routing_table.get(cidr),
+                None,
+                "we expect {cidr:?} to be absent"
+            );
+        }
+
+        routing_table.remove_cidr(cidrs[2]);
+        assert_eq!(routing_table.get(cidrs[2]), None);
+        assert_eq!(routing_table.get(cidrs[3]), Some(cidrs[3]));
+    }
+
+    pub fn coverage_test(mut routing_table: Box<dyn RoutingTable>) {
+        assert_eq!(0, routing_table.coverage());
+        assert_eq!(0.0, routing_table.coverage_ratio());
+
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(128, 0, 0, 0), 1).unwrap());
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap());
+        assert_eq!(1 << 31, routing_table.coverage());
+        assert_eq!(0.5, routing_table.coverage_ratio());
+
+        routing_table.add_cidr(Ipv4Cidr::new_host(Ipv4Addr::new(10, 0, 0, 1)));
+        assert_eq!((1 << 31) + 1, routing_table.coverage());
+
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap());
+        assert_eq!(1 << 32, routing_table.coverage());
+        assert_eq!(1.0, routing_table.coverage_ratio());
+    }
+
+    pub fn hierarchy_neighbors_test(mut routing_table: Box<dyn RoutingTable>) {
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 1, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 1, 128), 25).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 2, 0, 0), 16).unwrap(),
+        ];
+
+        for cidr in cidrs.iter().rev() {
+            routing_table.add_cidr(*cidr);
+        }
+
+        let test_cases = [
+            (cidrs[0], None, vec![cidrs[1], cidrs[5]]),
+            (cidrs[1], Some(cidrs[0]), vec![cidrs[2], cidrs[3]]),
+            (cidrs[2], Some(cidrs[1]), vec![cidrs[4]]),
+            (cidrs[4], Some(cidrs[2]), vec![]),
+            (
+                Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 17).unwrap(),
+                Some(cidrs[1]),
+                vec![cidrs[2], cidrs[3]],
+            ),
+            (
+                Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap(),
+                None,
+                vec![cidrs[0]],
+            ),
+            (
+                Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap(),
+                None,
+                vec![],
+            ),
+        ];
+
+        for (cidr, parent, children) in test_cases {
+            let result = routing_table.hierarchy_neighbors(cidr);
+
+            assert_eq!(
+                result,
+                (parent, children.clone()),
+                "we expect neighbors of {cidr:?} to be {parent:?} and {children:?}"
+            );
+        }
+    }
+
+    pub fn add_cidr_max_len_test(mut routing_table: Box<dyn RoutingTable>) {
+        let aggregate = Ipv4Cidr::new(Ipv4Addr::new(192, 168, 1, 0), 24).unwrap();
+        let host = Ipv4Cidr::new_host(Ipv4Addr::new(192, 168, 1, 1));
+
+        assert_eq!(routing_table.add_cidr_max_len(aggregate, 24), Ok(()));
+        assert_eq!(
+            routing_table.add_cidr_max_len(host, 24),
+            Err(NetworkParseError::PrefixTooLong)
+        );
+
+        assert_eq!(1, routing_table.size());
+        assert_eq!(
+            routing_table.find_exact_cidr(Ipv4Addr::new(192, 168, 1, 1)),
+            Some(aggregate)
+        );
+    }
+
+    pub fn drain_matching_test(mut routing_table: Box<dyn RoutingTable>) {
+        let hosts = [
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 0, 0, 1)),
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 0, 0, 2)),
+            Ipv4Cidr::new_host(Ipv4Addr::new(192, 168, 1, 1)),
+        ];
+        let networks = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 1, 0), 24).unwrap(),
+        ];
+
+        for cidr in hosts.iter().chain(networks.iter()) {
+            routing_table.add_cidr(*cidr);
+        }
+
+        let mut drained = routing_table.drain_matching(&mut |cidr| cidr.is_host());
+        drained.sort_unstable_by_key(|cidr| cidr.min());
+
+        assert_eq!(drained, hosts.to_vec());
+        assert_eq!(networks.len(), routing_table.size());
+        assert_eq!(
+            routing_table.find_exact_cidr(Ipv4Addr::new(192, 168, 1, 1)),
+            Some(networks[1])
+        );
+        assert_eq!(
+            routing_table.drain_matching(&mut |cidr| cidr.is_host()),
+            vec![]
+        );
+    }
+
+    pub fn next_free_subnet_test(mut routing_table: Box<dyn RoutingTable>) {
+        let parent = Ipv4Cidr::new(Ipv4Addr::new(10, 20, 0, 0), 16).unwrap();
+        let subnet =
+            |third: u8, len: u8| Ipv4Cidr::new(Ipv4Addr::new(10, 20, third, 0), len).unwrap();
+
+        assert_eq!(
+            routing_table.next_free_subnet(parent, 24),
+            Some(subnet(0, 24))
+        );
+
+        routing_table.add_cidr(subnet(0, 24));
+        routing_table.add_cidr(subnet(1, 24));
+        routing_table.add_cidr(subnet(2, 23));
+        routing_table.add_cidr(Ipv4Cidr::new_host(Ipv4Addr::new(10, 20, 4, 1)));
+        routing_table.add_cidr(subnet(6, 24));
+
+        let test_cases = [
+            (24, Some(subnet(5, 24))),
+            (23, Some(subnet(8, 23))),
+            (
+                25,
+                Some(Ipv4Cidr::new(Ipv4Addr::new(10, 20, 4, 128), 25).unwrap()),
+            ),
+            (15, None),
+        ];
+
+        for (len, expected) in test_cases {
+            let result = routing_table.next_free_subnet(parent, len);
+
+            assert_eq!(
+                result, expected,
+                "we expect first free /{len} in {parent:?} to be {expected:?}"
+            );
+        }
+
+        routing_table.add_cidr(parent);
+        assert_eq!(routing_table.next_free_subnet(parent, 24), None);
+    }
+
+    pub fn classify_address_test(mut routing_table: Box<dyn RoutingTable>) {
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(192, 168, 1, 0), 24).unwrap());
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 31).unwrap());
+        routing_table.add_cidr(Ipv4Cidr::new_host(Ipv4Addr::new(10, 0, 0, 8)));
+
+        let test_cases = [
+            (Ipv4Addr::new(192, 168, 1, 0), Some(AddrRole::Network)),
+            (Ipv4Addr::new(192, 168, 1, 1), Some(AddrRole::Host)),
+            (Ipv4Addr::new(192, 168, 1, 128), Some(AddrRole::Host)),
+            (Ipv4Addr::new(192, 168, 1, 254), Some(AddrRole::Host)),
+            (Ipv4Addr::new(192, 168, 1, 255), Some(AddrRole::Broadcast)),
+            (Ipv4Addr::new(10, 0, 0, 0), Some(AddrRole::Host)),
+            (Ipv4Addr::new(10, 0, 0, 1), Some(AddrRole::Host)),
+            (Ipv4Addr::new(10, 0, 0, 8), Some(AddrRole::Host)),
+            (Ipv4Addr::new(192, 168, 2, 0), None),
+        ];
+
+        for (addr, expected) in test_cases {
+            let result = routing_table.classify_address(addr);
+
+            assert_eq!(
+                result, expected,
+                "we expect {addr} to be classified as {expected:?}"
+            );
+        }
+    }
+
+    pub fn remove_within_test(mut routing_table: Box<dyn RoutingTable>) {
+        let scope = Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap();
+        let nested = [
+            scope,
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 1, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 255, 0), 24).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 2, 3)),
+        ];
+        let outside = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 2, 0, 0), 24).unwrap(),
+        ];
+
+        for cidr in nested.iter().chain(outside.iter()) {
+            routing_table.add_cidr(*cidr);
+        }
+
+        let mut removed = routing_table.remove_within(scope);
+        removed.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+
+        let mut expected = nested.to_vec();
+        expected.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+
+        assert_eq!(removed, expected);
+        assert_eq!(outside.len(), routing_table.size());
+
+        for cidr in nested {
+            assert_eq!(
+                routing_table.get(cidr),
+                None,
+                "we expect {cidr:?} to be removed"
+            );
+            assert_eq!(routing_table.find_exact_cidr(cidr.min()), Some(outside[0]));
+        }
+
+        assert_eq!(routing_table.remove_within(scope), vec![]);
+
+        let mut removed =
+            routing_table.remove_within(Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap());
+        removed.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+
+        assert_eq!(removed, outside.to_vec());
+        assert_eq!(0, routing_table.size());
+    }
+
+    pub fn approx_heap_bytes_test(mut routing_table: Box<dyn RoutingTable>) {
+        let initial = routing_table.approx_heap_bytes();
+        let mut previous = initial;
+
+        // Every step re-measures the whole table, which Miri cannot afford at full size.
+        let steps: u8 = if cfg!(miri) { 16 } else { 255 };
+        for third in 0..=steps {
+            routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, third, 0), 24).unwrap());
+            routing_table.add_cidr(Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, third, 1)));
+
+            let current = routing_table.approx_heap_bytes();
+            assert!(
+                current >= previous,
+                "we expect heap usage to grow monotonically, got {current} after {previous}"
+            );
+            previous = current;
+        }
+
+        assert!(
+            previous > initial,
+            "we expect heap usage to grow after inserting {} cidrs",
+            routing_table.size()
+        );
+    }
+
+    pub fn fingerprint_test(mut routing_table: Box<dyn RoutingTable>) {
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 2, 3)),
+        ];
+        let empty = routing_table.fingerprint();
+
+        let mut reference = ListRoutingTable::new();
+        for &cidr in cidrs.iter() {
+            routing_table.add_cidr(cidr);
+        }
+        for &cidr in cidrs.iter().rev() {
+            reference.add_cidr(cidr);
+        }
+
+        let fingerprint = routing_table.fingerprint();
+        assert_eq!(fingerprint, reference.fingerprint());
+        assert_ne!(fingerprint, empty);
+
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 9).unwrap());
+        assert_ne!(fingerprint, routing_table.fingerprint());
+
+        routing_table.remove_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 9).unwrap());
+        assert_eq!(fingerprint, routing_table.fingerprint());
+    }
+
+    pub fn duplicate_add_test(mut routing_table: Box<dyn RoutingTable>) {
+        let cidr = Ipv4Cidr::new(Ipv4Addr::new(192, 168, 1, 0), 24).unwrap();
+
+        routing_table.add_cidr(cidr);
+        routing_table.add_cidr(cidr);
+
+        assert_eq!(1, routing_table.size());
+        assert_eq!(vec![cidr], routing_table.iter().collect::<Vec<_>>());
+        assert_eq!(
+            vec![cidr],
+            routing_table.match_path(Ipv4Addr::new(192, 168, 1, 1))
+        );
+
+        routing_table.remove_cidr(cidr);
+        assert_eq!(0, routing_table.size());
+        assert_eq!(routing_table.get(cidr), None);
+    }
+
+    pub fn match_as_tcam_test(mut routing_table: Box<dyn RoutingTable>) {
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 23).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 2, 3)),
+        ];
+
+        assert_eq!(
+            routing_table.match_as_tcam(Ipv4Addr::new(10, 1, 2, 3)),
+            None
+        );
+
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        let test_cases = [
+            (Ipv4Addr::new(10, 1, 2, 3), (0x0a01_0203, u32::MAX)),
+            (Ipv4Addr::new(10, 1, 3, 200), (0x0a01_0200, 0xffff_fe00)),
+            (Ipv4Addr::new(10, 200, 0, 1), (0x0a00_0000, 0xff00_0000)),
+            (Ipv4Addr::new(192, 168, 0, 1), (0, 0)),
+        ];
+
+        for (addr, expected) in test_cases {
+            let (network, mask) = routing_table.match_as_tcam(addr).unwrap();
+
+            assert_eq!(
+                (network, mask),
+                expected,
+                "we expect {addr} to match {expected:x?}"
+            );
+            assert_eq!(network & mask, network);
+            assert_eq!(u32::from(addr) & mask, network);
+        }
+    }
+
+    pub fn best_fit_test(mut routing_table: Box<dyn RoutingTable>) {
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 2, 0, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 3, 0, 0), 26).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 4, 0, 1)),
+        ];
+
+        assert_eq!(routing_table.best_fit(1), None);
+
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        let test_cases = [
+            (0, Some(cidrs[4])),
+            (1, Some(cidrs[4])),
+            (2, Some(cidrs[3])),
+            (64, Some(cidrs[3])),
+            (65, Some(cidrs[2])),
+            (256, Some(cidrs[2])),
+            (257, Some(cidrs[0])),
+            (65536, Some(cidrs[0])),
+            (65537, None),
+        ];
+
+        for (min_addresses, expected) in test_cases {
+            let result = routing_table.best_fit(min_addresses);
+
+            assert_eq!(
+                result, expected,
+                "we expect best fit for {min_addresses} addresses to be {expected:?}"
+            );
+        }
+    }
+
+    pub fn covered_addresses_test(mut routing_table: Box<dyn RoutingTable>) {
+        assert_eq!(routing_table.covered_addresses().next(), None);
+
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 4), 30).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 6), 31).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 0, 0, 8)),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 30).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(192, 168, 0, 1)),
+            Ipv4Cidr::new_host(Ipv4Addr::new(255, 255, 255, 255)),
+        ];
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        let mut expected: Vec<Ipv4Addr> =
+            (0..=8).map(|last| Ipv4Addr::new(10, 0, 0, last)).collect();
+        expected.push(Ipv4Addr::new(192, 168, 0, 1));
+        expected.push(Ipv4Addr::new(255, 255, 255, 255));
+
+        assert_eq!(
+            routing_table.covered_addresses().collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    pub fn is_routable_test(mut routing_table: Box<dyn RoutingTable>) {
+        assert!(!routing_table.is_routable(Ipv4Addr::new(10, 0, 0, 1)));
+
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap());
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap());
+        routing_table.add_cidr(Ipv4Cidr::new_host(Ipv4Addr::new(192, 168, 0, 1)));
+
+        let test_cases = [
+            (Ipv4Addr::new(10, 1, 2, 3), true),
+            (Ipv4Addr::new(10, 200, 0, 1), true),
+            (Ipv4Addr::new(192, 168, 0, 1), true),
+            (Ipv4Addr::new(192, 168, 0, 2), false),
+            (Ipv4Addr::new(11, 0, 0, 0), false),
+            (Ipv4Addr::new(0, 0, 0, 0), false),
+        ];
+
+        for (addr, expected) in test_cases {
+            assert_eq!(
+                routing_table.is_routable(addr),
+                expected,
+                "we expect {addr} routability to be {expected}"
+            );
+        }
+
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap());
+        for (addr, _) in test_cases {
+            assert!(
+                routing_table.is_routable(addr),
+                "we expect {addr} to be routable via the default route"
+            );
+        }
+    }
+
+    pub fn has_same_cidrs_test(mut routing_table: Box<dyn RoutingTable>) {
+        let mut other = ListRoutingTable::new();
+        assert!(routing_table.has_same_cidrs(&other));
+
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap());
+        routing_table.add_cidr(Ipv4Cidr::new_host(Ipv4Addr::new(192, 168, 0, 1)));
+        other.add_cidr(Ipv4Cidr::new_host(Ipv4Addr::new(192, 168, 0, 1)));
+        other.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 128, 0, 0), 9).unwrap());
+        other.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 9).unwrap());
+
+        assert!(routing_table.covers(&other));
+        assert!(other.covers(routing_table.as_ref()));
+        assert!(!routing_table.has_same_cidrs(&other));
+        assert!(!other.has_same_cidrs(routing_table.as_ref()));
+
+        other.remove_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 128, 0, 0), 9).unwrap());
+        assert!(!routing_table.has_same_cidrs(&other));
+
+        other.remove_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 9).unwrap());
+        other.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap());
+        assert!(routing_table.has_same_cidrs(&other));
+        assert!(other.has_same_cidrs(routing_table.as_ref()));
+    }
+
+    pub fn find_by_range_test(mut routing_table: Box<dyn RoutingTable>) {
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 24).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 2, 3)),
+        ];
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        let test_cases = [
+            (
+                Ipv4Addr::new(0, 0, 0, 0),
+                Ipv4Addr::new(255, 255, 255, 255),
+                Some(cidrs[0]),
+            ),
+            (
+                Ipv4Addr::new(10, 0, 0, 0),
+                Ipv4Addr::new(10, 255, 255, 255),
+                Some(cidrs[1]),
+            ),
+            (
+                Ipv4Addr::new(10, 1, 2, 0),
+                Ipv4Addr::new(10, 1, 2, 255),
+                Some(cidrs[2]),
+            ),
+            (
+                Ipv4Addr::new(10, 1, 2, 3),
+                Ipv4Addr::new(10, 1, 2, 3),
+                Some(cidrs[3]),
+            ),
+            (
+                Ipv4Addr::new(10, 1, 3, 0),
+                Ipv4Addr::new(10, 1, 3, 255),
+                None,
+            ),
+            (
+                Ipv4Addr::new(10, 1, 2, 128),
+                Ipv4Addr::new(10, 1, 3, 127),
+                None,
+            ),
+            (
+                Ipv4Addr::new(10, 1, 2, 0),
+                Ipv4Addr::new(10, 1, 4, 255),
+                None,
+            ),
+            (
+                Ipv4Addr::new(10, 1, 2, 255),
+                Ipv4Addr::new(10, 1, 2, 0),
+                None,
+            ),
+        ];
+
+        for (min, max, expected) in test_cases {
+            let result = routing_table.find_by_range(min, max);
+
+            assert_eq!(
+                result, expected,
+                "we expect range {min}-{max} to match {expected:?}"
+            );
+        }
+    }
+
+    pub fn aggregated_test(mut routing_table: Box<dyn RoutingTable>) {
+        assert_eq!(routing_table.aggregated(), vec![]);
+
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 1, 128), 25).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 1, 0), 25).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(192, 168, 1, 7)),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+        ];
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        assert_eq!(
+            routing_table.aggregated(),
+            vec![
+                Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+                Ipv4Cidr::new(Ipv4Addr::new(192, 168, 1, 0), 24).unwrap(),
+            ]
+        );
+        assert_eq!(cidrs.len(), routing_table.size());
+    }
+
+    pub fn lookup_or_default_test(mut routing_table: Box<dyn RoutingTable>) {
+        let gateway = Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap();
+        let cidr = Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap();
+        routing_table.add_cidr(cidr);
+
+        assert_eq!(
+            routing_table.lookup_or_default(Ipv4Addr::new(10, 1, 2, 3), gateway),
+            cidr
+        );
+        assert_eq!(
+            routing_table.lookup_or_default(Ipv4Addr::new(192, 168, 0, 1), gateway),
+            gateway
+        );
+    }
+
+    pub fn leaf_pairs_test(mut routing_table: Box<dyn RoutingTable>) {
+        assert_eq!(routing_table.leaf_pairs(), vec![]);
+
+        let cidrs = [
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 2, 3)),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 16).unwrap(),
+        ];
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        let pairs = routing_table.leaf_pairs();
+        assert_eq!(
+            pairs,
+            vec![
+                (0, 0),
+                (0x0a00_0000, 8),
+                (0x0a00_0000, 16),
+                (0x0a01_0203, 32)
+            ]
+        );
+
+        let mut rebuilt = ListRoutingTable::new();
+        for (bits, len) in pairs {
+            rebuilt.add_cidr(Ipv4Cidr::from_bits(bits, len).unwrap());
+        }
+        assert!(rebuilt.has_same_cidrs(routing_table.as_ref()));
+    }
+
+    pub fn routes_in_range_test(mut routing_table: Box<dyn RoutingTable>) {
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 4, 0), 23).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 6, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 9, 128), 25).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 10, 0), 24).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(192, 168, 0, 1)),
+        ];
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        let test_cases = [
+            (
+                Ipv4Addr::new(10, 0, 5, 0),
+                Ipv4Addr::new(10, 0, 9, 255),
+                vec![cidrs[0], cidrs[1], cidrs[2], cidrs[3]],
+            ),
+            (
+                Ipv4Addr::new(10, 0, 9, 255),
+                Ipv4Addr::new(10, 0, 10, 0),
+                vec![cidrs[0], cidrs[3], cidrs[4]],
+            ),
+            (
+                Ipv4Addr::new(11, 0, 0, 0),
+                Ipv4Addr::new(192, 168, 0, 0),
+                vec![],
+            ),
+            (
+                Ipv4Addr::new(0, 0, 0, 0),
+                Ipv4Addr::new(255, 255, 255, 255),
+                cidrs.to_vec(),
+            ),
+            (
+                Ipv4Addr::new(192, 168, 0, 1),
+                Ipv4Addr::new(192, 168, 0, 1),
+                vec![cidrs[5]],
+            ),
+            (
+                Ipv4Addr::new(10, 0, 10, 0),
+                Ipv4Addr::new(10, 0, 9, 0),
+                vec![],
+            ),
+        ];
+
+        for (start, end, expected) in test_cases {
+            let result = routing_table.routes_in_range(start, end);
+
+            assert_eq!(
+                result, expected,
+                "we expect routes touching {start}-{end} to be {expected:?}"
+            );
+        }
+    }
+
+    pub fn routes_of_length_test(mut routing_table: Box<dyn RoutingTable>) {
+        let slash_24s = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 1, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 2, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 24).unwrap(),
+        ];
+        let others = [
+            Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 1, 0), 25).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 23).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 0, 1, 1)),
+        ];
+        for cidr in slash_24s.iter().rev().chain(others.iter()) {
+            routing_table.add_cidr(*cidr);
+        }
+
+        assert_eq!(routing_table.routes_of_length(24), slash_24s.to_vec());
+        assert_eq!(routing_table.routes_of_length(0), vec![others[0]]);
+        assert_eq!(routing_table.routes_of_length(32), vec![others[4]]);
+        assert_eq!(routing_table.routes_of_length(16), vec![]);
+        assert_eq!(routing_table.routes_of_length(33), vec![]);
+    }
+
+    pub fn complement_within_test(mut routing_table: Box<dyn RoutingTable>) {
+        let scope = Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap();
+        assert_eq!(routing_table.complement_within(scope), vec![scope]);
+
+        let blocklist = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 5, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 255, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap(),
+        ];
+        for cidr in blocklist {
+            routing_table.add_cidr(cidr);
+        }
+
+        let expected: Vec<Ipv4Cidr> = [
+            "10.1.1.0/24",
+            "10.1.2.0/23",
+            "10.1.4.0/24",
+            "10.1.6.0/23",
+            "10.1.8.0/21",
+            "10.1.16.0/20",
+            "10.1.32.0/19",
+            "10.1.64.0/18",
+            "10.1.128.0/18",
+            "10.1.192.0/19",
+            "10.1.224.0/20",
+            "10.1.240.0/21",
+            "10.1.248.0/22",
+            "10.1.252.0/23",
+            "10.1.254.0/24",
+        ]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+        assert_eq!(routing_table.complement_within(scope), expected);
+
+        let blocked = Ipv4Cidr::new(Ipv4Addr::new(10, 1, 5, 128), 25).unwrap();
+        assert_eq!(routing_table.complement_within(blocked), vec![]);
+
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap());
+        assert_eq!(routing_table.complement_within(scope), vec![]);
+
+        let everything = Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap();
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(128, 0, 0, 0), 1).unwrap());
+        assert_eq!(
+            routing_table.complement_within(everything),
+            vec![
+                Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 5).unwrap(),
+                Ipv4Cidr::new(Ipv4Addr::new(8, 0, 0, 0), 7).unwrap(),
+                Ipv4Cidr::new(Ipv4Addr::new(11, 0, 0, 0), 8).unwrap(),
+                Ipv4Cidr::new(Ipv4Addr::new(12, 0, 0, 0), 6).unwrap(),
+                Ipv4Cidr::new(Ipv4Addr::new(16, 0, 0, 0), 4).unwrap(),
+                Ipv4Cidr::new(Ipv4Addr::new(32, 0, 0, 0), 3).unwrap(),
+                Ipv4Cidr::new(Ipv4Addr::new(64, 0, 0, 0), 2).unwrap(),
+            ]
+        );
+    }
+
+    pub fn iter_with_depth_test(mut routing_table: Box<dyn RoutingTable>) {
+        assert_eq!(routing_table.iter_with_depth(), vec![]);
+
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 2, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap(),
+        ];
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        assert_eq!(
+            routing_table.iter_with_depth(),
+            vec![
+                (cidrs[1], 0),
+                (cidrs[2], 1),
+                (cidrs[0], 2),
+                (cidrs[3], 1),
+                (cidrs[4], 0),
+            ]
+        );
+    }
+
+    pub fn to_fib_24_test(mut routing_table: Box<dyn RoutingTable>) {
+        let fib = routing_table.to_fib_24();
+        assert_eq!(fib.len(), 1 << 24);
+        assert!(fib.iter().all(|&entry| entry == FIB_NO_ROUTE));
+
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 3, 128), 25).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 23).unwrap(),
+        ];
+        for cidr in cidrs.iter().rev() {
+            routing_table.add_cidr(*cidr);
+        }
+
+        let block = |a: u8, b: u8, c: u8| (u32::from(Ipv4Addr::new(a, b, c, 0)) >> 8) as usize;
+        let fib = routing_table.to_fib_24();
+        let test_cases = [
+            (block(10, 0, 0), 0),
+            (block(10, 255, 255), 0),
+            (block(10, 1, 0), 1),
+            (block(10, 1, 255), 1),
+            (block(10, 1, 2), 2),
+            (block(10, 1, 3), FIB_DESCEND),
+            (block(192, 168, 0), 4),
+            (block(192, 168, 1), 4),
+            (block(192, 168, 2), FIB_NO_ROUTE),
+            (block(9, 255, 255), FIB_NO_ROUTE),
+            (block(11, 0, 0), FIB_NO_ROUTE),
+        ];
+
+        for (block, expected) in test_cases {
+            assert_eq!(
+                fib[block], expected,
+                "we expect fib entry {block:#x} to be {expected}"
+            );
+        }
+        assert_eq!(
+            routing_table.leaf_pairs()[3],
+            (u32::from(cidrs[3].min()), 25)
+        );
+    }
+
+    pub fn filter_routable_test(mut routing_table: Box<dyn RoutingTable>) {
+        let addrs = [
+            Ipv4Addr::new(10, 1, 2, 3),
+            Ipv4Addr::new(11, 0, 0, 1),
+            Ipv4Addr::new(192, 168, 0, 1),
+            Ipv4Addr::new(192, 168, 0, 2),
+            Ipv4Addr::new(10, 255, 255, 255),
+            Ipv4Addr::new(11, 0, 0, 1),
+        ];
+        assert_eq!(
+            routing_table.filter_routable(&addrs),
+            vec![false; addrs.len()]
+        );
+        assert_eq!(routing_table.filter_routable(&[]), vec![]);
+
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap());
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap());
+        routing_table.add_cidr(Ipv4Cidr::new_host(Ipv4Addr::new(192, 168, 0, 1)));
+
+        assert_eq!(
+            routing_table.filter_routable(&addrs),
+            vec![true, false, true, false, true, false]
+        );
+    }
+
+    pub fn longest_prefix_match_detailed_test(mut routing_table: Box<dyn RoutingTable>) {
+        let subnet = Ipv4Cidr::new(Ipv4Addr::new(192, 168, 1, 0), 24).unwrap();
+        let host = Ipv4Cidr::new_host(Ipv4Addr::new(192, 168, 1, 7));
+        routing_table.add_cidr(subnet);
+        routing_table.add_cidr(host);
+
+        let test_cases = [
+            (Ipv4Addr::new(192, 168, 1, 7), Some((host, true))),
+            (Ipv4Addr::new(192, 168, 1, 8), Some((subnet, false))),
+            (Ipv4Addr::new(192, 168, 1, 0), Some((subnet, false))),
+            (Ipv4Addr::new(192, 168, 2, 7), None),
+        ];
+
+        for (addr, expected) in test_cases {
+            assert_eq!(
+                routing_table.longest_prefix_match_detailed(addr),
+                expected,
+                "we expect the detailed match for {addr} to be {expected:?}"
+            );
+        }
+    }
+
+    pub fn longest_prefix_match_capped_test(mut routing_table: Box<dyn RoutingTable>) {
+        let aggregate = Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap();
+        let subnet = Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 24).unwrap();
+        let host = Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 2, 3));
+        for cidr in [host, aggregate, subnet] {
+            routing_table.add_cidr(cidr);
+        }
+
+        let addr = Ipv4Addr::new(10, 1, 2, 3);
+        let test_cases = [
+            (32, Some(host)),
+            (33, Some(host)),
+            (31, Some(subnet)),
+            (24, Some(subnet)),
+            (23, Some(aggregate)),
+            (8, Some(aggregate)),
+            (7, None),
+            (0, None),
+        ];
+
+        for (max_len, expected) in test_cases {
+            assert_eq!(
+                routing_table.longest_prefix_match_capped(addr, max_len),
+                expected,
+                "we expect {addr} capped at /{max_len} to match {expected:?}"
+            );
+        }
+
+        assert_eq!(
+            routing_table.longest_prefix_match_capped(Ipv4Addr::new(10, 1, 3, 3), 24),
+            Some(aggregate)
+        );
+        assert_eq!(
+            routing_table.longest_prefix_match_capped(Ipv4Addr::new(11, 0, 0, 0), 32),
+            None
+        );
+    }
+
+    pub fn count_within_test(mut routing_table: Box<dyn RoutingTable>) {
+        let scope = Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap();
+        assert_eq!(routing_table.count_within(scope), 0);
+
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            scope,
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 1, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 200, 0), 24).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 200, 1)),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 2, 0, 0), 24).unwrap(),
+        ];
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        assert_eq!(routing_table.count_within(scope), 4);
+        assert_eq!(routing_table.count_within(cidrs[0]), 6);
+        assert_eq!(routing_table.count_within(cidrs[5]), 0);
+        assert_eq!(
+            routing_table.count_within(Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap()),
+            cidrs.len()
+        );
+        assert_eq!(
+            routing_table.count_within(Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap()),
+            0
+        );
+    }
+
+    pub fn leaf_pairs_be_test(mut routing_table: Box<dyn RoutingTable>) {
+        assert_eq!(routing_table.leaf_pairs_be(), vec![]);
+
+        let cidrs = [
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 2, 3)),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap(),
+        ];
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+
+        assert_eq!(
+            routing_table.leaf_pairs_be(),
+            vec![
+                ([0, 0, 0, 0], 0),
+                ([10, 1, 2, 3], 32),
+                ([192, 168, 0, 0], 16)
+            ]
+        );
+        for (octets, len) in routing_table.leaf_pairs_be() {
+            let cidr = Ipv4Cidr::from_octets(octets, len).unwrap();
+
+            assert_eq!(octets, cidr.min().octets());
+            assert!(routing_table.get(cidr).is_some());
+        }
+    }
+
+    pub fn aggregates_exceeding_test(mut routing_table: Box<dyn RoutingTable>) {
+        assert_eq!(routing_table.aggregates_exceeding(16, 0), vec![]);
+
+        let hotspot = Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap();
+        let quiet = Ipv4Cidr::new(Ipv4Addr::new(10, 2, 0, 0), 16).unwrap();
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap());
+        routing_table.add_cidr(hotspot);
+        for third in [0, 1, 2, 3, 200] {
+            routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 1, third, 0), 24).unwrap());
+        }
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 2, 0, 0), 24).unwrap());
+        routing_table.add_cidr(Ipv4Cidr::new_host(Ipv4Addr::new(10, 2, 0, 1)));
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 24).unwrap());
+
+        assert_eq!(
+            routing_table.aggregates_exceeding(16, 3),
+            vec![(hotspot, 5)]
+        );
+        assert_eq!(routing_table.aggregates_exceeding(16, 5), vec![]);
+        assert_eq!(
+            routing_table.aggregates_exceeding(16, 1),
+            vec![(hotspot, 5), (quiet, 2)]
+        );
+        assert_eq!(
+            routing_table.aggregates_exceeding(8, 5),
+            vec![(Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(), 8)]
+        );
+        assert_eq!(routing_table.aggregates_exceeding(32, 0), vec![]);
+        assert_eq!(routing_table.aggregates_exceeding(33, 0), vec![]);
+    }
+
+    pub fn match_with_backup_test(mut routing_table: Box<dyn RoutingTable>) {
+        let primary = Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 25).unwrap();
+        let backup = Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 128), 25).unwrap();
+        let lonely = Ipv4Cidr::new(Ipv4Addr::new(10, 0, 2, 0), 24).unwrap();
+        let default_route = Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap();
+        for cidr in [primary, backup, lonely] {
+            routing_table.add_cidr(cidr);
+        }
+
+        let test_cases = [
+            (Ipv4Addr::new(10, 0, 0, 1), Some((primary, Some(backup)))),
+            (Ipv4Addr::new(10, 0, 0, 200), Some((backup, Some(primary)))),
+            (Ipv4Addr::new(10, 0, 2, 1), Some((lonely, None))),
+            (Ipv4Addr::new(10, 0, 3, 1), None),
+        ];
+
+        for (addr, expected) in test_cases {
+            assert_eq!(
+                routing_table.match_with_backup(addr),
+                expected,
+                "we expect {addr} to match {expected:?}"
+            );
+        }
+
+        routing_table.add_cidr(default_route);
+        assert_eq!(
+            routing_table.match_with_backup(Ipv4Addr::new(10, 0, 3, 1)),
+            Some((default_route, None))
+        );
+    }
+
+    pub fn cidrs_sorted_test(mut routing_table: Box<dyn RoutingTable>) {
+        assert_eq!(routing_table.cidrs_sorted(), vec![]);
+
+        for cidr in unsorted_cidrs() {
+            routing_table.add_cidr(cidr);
+        }
+
+        assert_eq!(routing_table.cidrs_sorted(), sorted_cidrs());
+    }
+
+    // Takes the table by value since `remove_cidrs` is not available on `dyn RoutingTable`.
+    pub fn remove_cidrs_test<T: RoutingTable>(mut routing_table: T) {
+        let cidrs = unsorted_cidrs();
+        for &cidr in cidrs.iter() {
+            routing_table.add_cidr(cidr);
+        }
+
+        let absent = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 9).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(192, 168, 0, 1)),
+        ];
+        let batch = [cidrs[0], absent[0], cidrs[3], cidrs[3], absent[1], cidrs[5]];
+
+        assert_eq!(routing_table.remove_cidrs(batch), 3);
+        assert_eq!(routing_table.size(), cidrs.len() - 3);
+        for cidr in [cidrs[0], cidrs[3], cidrs[5]] {
+            assert_eq!(routing_table.get(cidr), None);
+        }
+        assert_eq!(routing_table.get(cidrs[1]), Some(cidrs[1]));
+
+        assert_eq!(routing_table.remove_cidrs(absent), 0);
+        assert_eq!(routing_table.remove_cidrs(Vec::new()), 0);
+        assert_eq!(routing_table.remove_cidrs(cidrs.clone()), cidrs.len() - 3);
+        assert_eq!(routing_table.size(), 0);
+    }
+
+    pub fn default_free_test(mut routing_table: Box<dyn RoutingTable>) {
+        let default_route = Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap();
+        assert!(routing_table.is_default_free());
+        assert!(!routing_table.is_fully_covered_without_default());
+
+        routing_table.add_cidr(default_route);
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 1).unwrap());
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(128, 0, 0, 0), 2).unwrap());
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(224, 0, 0, 0), 3).unwrap());
+        assert!(routing_table.has_default_route());
+        assert!(!routing_table.is_default_free());
+        assert!(!routing_table.is_fully_covered_without_default());
+
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(192, 0, 0, 0), 3).unwrap());
+        assert!(routing_table.is_fully_covered_without_default());
+
+        routing_table.remove_cidr(default_route);
+        assert!(routing_table.is_default_free());
+        assert!(routing_table.is_fully_covered_without_default());
+
+        routing_table.remove_cidr(Ipv4Cidr::new(Ipv4Addr::new(128, 0, 0, 0), 2).unwrap());
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(128, 0, 0, 0), 3).unwrap());
+        assert!(!routing_table.is_fully_covered_without_default());
+    }
+
+    // Mixed lengths and shared network addresses, deliberately out of order.
+    pub fn unsorted_cidrs() -> Vec<Ipv4Cidr> {
+        vec![
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 0, 0, 0)),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(172, 16, 0, 0), 12).unwrap(),
+        ]
+    }
+
+    // The expected order of `unsorted_cidrs`: by network address, then by length.
+    pub fn sorted_cidrs() -> Vec<Ipv4Cidr> {
+        vec![
+            Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 24).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(10, 0, 0, 0)),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(172, 16, 0, 0), 12).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_fingerprint_shared_network() {
+        let network = Ipv4Addr::new(192, 168, 0, 0);
+        let cidrs: Vec<Ipv4Cidr> = [13, 15, 16, 17, 24, 31, 32]
+            .into_iter()
+            .map(|len| Ipv4Cidr::new(network, len).unwrap())
+            .collect();
+
+        let mut singles = Vec::new();
+        let mut cumulative = vec![ListRoutingTable::new().fingerprint()];
+        let mut all = ListRoutingTable::new();
+        for &cidr in &cidrs {
+            let mut single = ListRoutingTable::new();
+            single.add_cidr(cidr);
+            singles.push(single.fingerprint());
+
+            all.add_cidr(cidr);
+            cumulative.push(all.fingerprint());
+        }
+
+        for fingerprints in [singles, cumulative] {
+            let mut distinct = fingerprints.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+
+            assert_eq!(
+                distinct.len(),
+                fingerprints.len(),
+                "we expect prefixes sharing {network} to fingerprint differently"
+            );
+        }
+    }
+
+    #[test]
+    fn test_new_table() {
+        let cidr = Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap();
+
+        for kind in [TableKind::List, TableKind::Hash, TableKind::Trie] {
+            let mut routing_table = new_table(kind);
+            assert_eq!(
+                0,
+                routing_table.size(),
+                "we expect a new {kind:?} table to be empty"
+            );
+
+            routing_table.add_cidr(cidr);
+            assert_eq!(1, routing_table.size());
+            assert_eq!(
+                routing_table.find_exact_cidr(Ipv4Addr::new(10, 1, 2, 3)),
+                Some(cidr),
+                "we expect a {kind:?} table to find inserted cidrs"
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_auto() {
+        let small = vec![
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap(),
+        ];
+        let skewed: Vec<Ipv4Cidr> = (0..=255)
+            .map(|third| Ipv4Cidr::new(Ipv4Addr::new(10, 1, third, 0), 24).unwrap())
+            .chain(small.iter().copied())
+            .collect();
+        let mut mixed: Vec<Ipv4Cidr> = (0..=255u8)
+            .map(|third| {
+                let len = 17 + third % 16;
+                Ipv4Cidr::containing(Ipv4Addr::new(10, 1, third, 0), len).unwrap()
+            })
+            .chain(small.iter().copied())
+            .collect();
+        mixed.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+        mixed.dedup();
+
+        for cidrs in [small, skewed, mixed] {
+            let routing_table = build_auto(&cidrs);
+
+            assert_eq!(cidrs.len(), routing_table.size());
+            for cidr in &cidrs {
+                let expected = cidrs
+                    .iter()
+                    .filter(|other| other.contains(cidr.min()))
+                    .max_by_key(|other| other.prefix_len())
+                    .copied();
+
+                assert_eq!(
+                    routing_table.find_exact_cidr(cidr.min()),
+                    expected,
+                    "we expect lookup of {} to match {expected:?}",
+                    cidr.min()
+                );
+            }
+
+            assert_eq!(
+                routing_table.find_exact_cidr(Ipv4Addr::new(11, 0, 0, 0)),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let parse = |cidrs: &[&str]| -> Vec<Ipv4Cidr> {
+            let mut cidrs: Vec<Ipv4Cidr> = cidrs.iter().map(|s| s.parse().unwrap()).collect();
+            cidrs.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+            cidrs
+        };
+        let sorted = |routing_table: &dyn RoutingTable| -> Vec<Ipv4Cidr> {
+            let mut cidrs: Vec<Ipv4Cidr> = routing_table.iter().collect();
+            cidrs.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+            cidrs
+        };
+
+        let mut a = HashRoutingTable::new();
+        let mut b = TrieRoutingTable::new();
+        for cidr in parse(&["10.0.0.0/8", "10.1.0.0/16", "192.168.0.0/16"]) {
+            a.add_cidr(cidr);
+        }
+        for cidr in parse(&["10.1.0.0/16", "172.16.0.0/12", "192.168.0.0/24"]) {
+            b.add_cidr(cidr);
+        }
+
+        let united: ListRoutingTable = union(&a, &b);
+        let common: TrieRoutingTable = intersection(&a, &b);
+        let only_a: HashRoutingTable = difference(&a, &b);
+        let only_b: ListRoutingTable = difference(&b, &a);
+
+        assert_eq!(
+            sorted(&united),
+            parse(&[
+                "10.0.0.0/8",
+                "10.1.0.0/16",
+                "172.16.0.0/12",
+                "192.168.0.0/16",
+                "192.168.0.0/24",
+            ])
+        );
+        assert_eq!(sorted(&common), parse(&["10.1.0.0/16"]));
+        assert_eq!(sorted(&only_a), parse(&["10.0.0.0/8", "192.168.0.0/16"]));
+        assert_eq!(sorted(&only_b), parse(&["172.16.0.0/12", "192.168.0.0/24"]));
+        assert_eq!(3, a.size());
+        assert_eq!(3, b.size());
+    }
 }
@@ -0,0 +1,160 @@
+use super::RoutingTable;
+use crate::Ipv4Cidr;
+use std::net::Ipv4Addr;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum RouteEvent {
+    Added(Ipv4Cidr),
+    Removed(Ipv4Cidr),
+}
+
+// Events fire only when the inner table actually changes, so re-adding a stored cidr or
+// removing a missing one stays silent.
+pub struct ObservableRoutingTable<T, F> {
+    inner: T,
+    on_change: F,
+}
+
+impl<T: RoutingTable, F: FnMut(RouteEvent)> ObservableRoutingTable<T, F> {
+    pub fn new(inner: T, on_change: F) -> Self {
+        Self { inner, on_change }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: RoutingTable, F: FnMut(RouteEvent)> RoutingTable for ObservableRoutingTable<T, F> {
+    fn add_cidr(&mut self, cidr: Ipv4Cidr) {
+        if self.inner.get(cidr).is_none() {
+            self.inner.add_cidr(cidr);
+            (self.on_change)(RouteEvent::Added(cidr));
+        }
+    }
+
+    fn remove_cidr(&mut self, cidr: Ipv4Cidr) {
+        if self.inner.get(cidr).is_some() {
+            self.inner.remove_cidr(cidr);
+            (self.on_change)(RouteEvent::Removed(cidr));
+        }
+    }
+
+    fn drain_matching(&mut self, f: &mut dyn FnMut(Ipv4Cidr) -> bool) -> Vec<Ipv4Cidr> {
+        let removed = self.inner.drain_matching(f);
+        for &cidr in removed.iter() {
+            (self.on_change)(RouteEvent::Removed(cidr));
+        }
+
+        removed
+    }
+
+    fn remove_within(&mut self, scope: Ipv4Cidr) -> Vec<Ipv4Cidr> {
+        let removed = self.inner.remove_within(scope);
+        for &cidr in removed.iter() {
+            (self.on_change)(RouteEvent::Removed(cidr));
+        }
+
+        removed
+    }
+
+    fn find_exact_cidr(&self, addr: Ipv4Addr) -> Option<Ipv4Cidr> {
+        self.inner.find_exact_cidr(addr)
+    }
+
+    fn get(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.inner.get(cidr)
+    }
+
+    fn is_routable(&self, addr: Ipv4Addr) -> bool {
+        self.inner.is_routable(addr)
+    }
+
+    fn find_covering(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.inner.find_covering(cidr)
+    }
+
+    fn find_parent(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.inner.find_parent(cidr)
+    }
+
+    fn match_path(&self, addr: Ipv4Addr) -> Vec<Ipv4Cidr> {
+        self.inner.match_path(addr)
+    }
+
+    fn hierarchy_neighbors(&self, cidr: Ipv4Cidr) -> (Option<Ipv4Cidr>, Vec<Ipv4Cidr>) {
+        self.inner.hierarchy_neighbors(cidr)
+    }
+
+    fn size(&self) -> usize {
+        self.inner.size()
+    }
+
+    fn approx_heap_bytes(&self) -> usize {
+        self.inner.approx_heap_bytes()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Ipv4Cidr> + '_> {
+        self.inner.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ObservableRoutingTable, RouteEvent};
+    use crate::{
+        routing_table::tests::{complex_test, simple_test},
+        HashRoutingTable, Ipv4Cidr, RoutingTable, TrieRoutingTable,
+    };
+    use std::{cell::RefCell, net::Ipv4Addr};
+
+    #[test]
+    fn test_observable_simple() {
+        simple_test(Box::new(ObservableRoutingTable::new(
+            TrieRoutingTable::new(),
+            |_| {},
+        )));
+    }
+
+    #[test]
+    fn test_observable_complex() {
+        complex_test(Box::new(ObservableRoutingTable::new(
+            HashRoutingTable::new(),
+            |_| {},
+        )));
+    }
+
+    #[test]
+    fn test_observable_route_events() {
+        let events = RefCell::new(Vec::new());
+        let mut routing_table = ObservableRoutingTable::new(TrieRoutingTable::new(), |event| {
+            events.borrow_mut().push(event)
+        });
+
+        let aggregate = Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap();
+        let subnet = Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap();
+        let host = Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 2, 3));
+
+        routing_table.add_cidr(aggregate);
+        routing_table.add_cidr(subnet);
+        routing_table.add_cidr(aggregate);
+        routing_table.add_cidr(host);
+        routing_table.remove_cidr(aggregate);
+        routing_table.remove_cidr(aggregate);
+        routing_table.remove_within(subnet);
+
+        assert_eq!(routing_table.size(), 0);
+        drop(routing_table);
+        assert_eq!(
+            events.into_inner(),
+            vec![
+                RouteEvent::Added(aggregate),
+                RouteEvent::Added(subnet),
+                RouteEvent::Added(host),
+                RouteEvent::Removed(aggregate),
+                RouteEvent::Removed(subnet),
+                RouteEvent::Removed(host),
+            ]
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{Ipv4Cidr, RoutingTable};
+use std::net::Ipv4Addr;
+
+// Matches of equal length for one address are always the same prefix, so priorities break
+// ties between routes to the same cidr that were added with different priorities.
+#[derive(Default)]
+pub struct PriorityListRoutingTable {
+    routes: Vec<(Ipv4Cidr, u32)>,
+}
+
+impl PriorityListRoutingTable {
+    pub fn new() -> Self {
+        Self { routes: Vec::new() }
+    }
+
+    pub fn add_route(&mut self, cidr: Ipv4Cidr, priority: u32) {
+        if !self.routes.contains(&(cidr, priority)) {
+            self.routes.push((cidr, priority));
+        }
+    }
+
+    pub fn remove_route(&mut self, cidr: Ipv4Cidr, priority: u32) {
+        self.routes.retain(|route| route != &(cidr, priority));
+    }
+
+    // Priorities are dropped, so a cidr stored under several of them appears once.
+    pub fn into_sorted_vec(self) -> Vec<Ipv4Cidr> {
+        self.sorted_cidrs()
+    }
+
+    pub fn find_exact(&self, addr: Ipv4Addr) -> Option<(Ipv4Cidr, u32)> {
+        self.find_longest(addr, 32)
+    }
+
+    fn find_longest(&self, addr: Ipv4Addr, max_len: u8) -> Option<(Ipv4Cidr, u32)> {
+        self.routes
+            .iter()
+            .filter(|(cidr, _)| cidr.prefix_len() <= max_len && cidr.contains(addr))
+            .max_by_key(|(cidr, priority)| (cidr.prefix_len(), *priority))
+            .copied()
+    }
+
+    fn sorted_cidrs(&self) -> Vec<Ipv4Cidr> {
+        let mut cidrs: Vec<Ipv4Cidr> = self.routes.iter().map(|&(cidr, _)| cidr).collect();
+        cidrs.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+        cidrs.dedup();
+        cidrs
+    }
+}
+
+impl RoutingTable for PriorityListRoutingTable {
+    fn add_cidr(&mut self, cidr: Ipv4Cidr) {
+        self.add_route(cidr, 0);
+    }
+
+    fn remove_cidr(&mut self, cidr: Ipv4Cidr) {
+        self.routes.retain(|(cur, _)| cur != &cidr);
+    }
+
+    fn find_exact_cidr(&self, addr: Ipv4Addr) -> Option<Ipv4Cidr> {
+        self.find_exact(addr).map(|(cidr, _)| cidr)
+    }
+
+    fn get(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.routes
+            .iter()
+            .find(|(cur, _)| cur == &cidr)
+            .map(|&(cur, _)| cur)
+    }
+
+    fn find_covering(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.find_longest(cidr.min(), cidr.prefix_len())
+            .map(|(cidr, _)| cidr)
+    }
+
+    fn find_parent(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        match cidr.prefix_len() {
+            0 => None,
+            len => self.find_longest(cidr.min(), len - 1).map(|(cidr, _)| cidr),
+        }
+    }
+
+    fn match_path(&self, addr: Ipv4Addr) -> Vec<Ipv4Cidr> {
+        let mut path: Vec<Ipv4Cidr> = self
+            .sorted_cidrs()
+            .into_iter()
+            .filter(|cidr| cidr.contains(addr))
+            .collect();
+
+        path.sort_unstable_by_key(|cidr| cidr.prefix_len());
+        path
+    }
+
+    fn size(&self) -> usize {
+        self.sorted_cidrs().len()
+    }
+
+    fn approx_heap_bytes(&self) -> usize {
+        self.routes.capacity() * size_of::<(Ipv4Cidr, u32)>()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Ipv4Cidr> + '_> {
+        Box::new(self.sorted_cidrs().into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PriorityListRoutingTable;
+    use crate::{
+        routing_table::tests::{
+            complex_test, duplicate_add_test, empty_test, match_path_test, simple_test,
+            sorted_cidrs, unsorted_cidrs,
+        },
+        Ipv4Cidr, RoutingTable,
+    };
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_priority_list_empty_case() {
+        empty_test(Box::new(PriorityListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_priority_list_simple() {
+        simple_test(Box::new(PriorityListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_priority_list_complex() {
+        complex_test(Box::new(PriorityListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_priority_list_match_path() {
+        match_path_test(Box::new(PriorityListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_priority_list_duplicate_add() {
+        duplicate_add_test(Box::new(PriorityListRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_priority_list_into_sorted_vec() {
+        assert_eq!(PriorityListRoutingTable::new().into_sorted_vec(), vec![]);
+
+        let mut routing_table = PriorityListRoutingTable::new();
+        for cidr in unsorted_cidrs() {
+            routing_table.add_cidr(cidr);
+        }
+        routing_table.add_route(unsorted_cidrs()[0], 7);
+
+        assert_eq!(routing_table.into_sorted_vec(), sorted_cidrs());
+    }
+
+    #[test]
+    fn test_priority_list_ties() {
+        let mut routing_table = PriorityListRoutingTable::new();
+        let aggregate = Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap();
+        let cidr = Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap();
+        let addr = Ipv4Addr::new(10, 1, 2, 3);
+
+        routing_table.add_route(aggregate, 100);
+        routing_table.add_route(cidr, 5);
+        routing_table.add_route(cidr, 20);
+        routing_table.add_route(cidr, 10);
+
+        assert_eq!(routing_table.find_exact(addr), Some((cidr, 20)));
+        assert_eq!(routing_table.find_exact_cidr(addr), Some(cidr));
+        assert_eq!(
+            routing_table.find_exact(Ipv4Addr::new(10, 2, 0, 1)),
+            Some((aggregate, 100))
+        );
+        assert_eq!(2, routing_table.size());
+
+        routing_table.remove_route(cidr, 20);
+        assert_eq!(routing_table.find_exact(addr), Some((cidr, 10)));
+
+        routing_table.remove_cidr(cidr);
+        assert_eq!(routing_table.find_exact(addr), Some((aggregate, 100)));
+        assert_eq!(1, routing_table.size());
+    }
+}
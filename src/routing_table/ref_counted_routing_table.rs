@@ -0,0 +1,192 @@
+use super::RoutingTable;
+use crate::Ipv4Cidr;
+use std::{collections::HashMap, net::Ipv4Addr};
+
+#[derive(Default)]
+pub struct RefCountedRoutingTable<T> {
+    inner: T,
+    counts: HashMap<Ipv4Cidr, usize>,
+}
+
+impl<T: RoutingTable> RefCountedRoutingTable<T> {
+    pub fn new(inner: T) -> Self {
+        let counts = inner.iter().map(|cidr| (cidr, 1)).collect();
+
+        Self { inner, counts }
+    }
+
+    pub fn ref_count(&self, cidr: Ipv4Cidr) -> usize {
+        self.counts.get(&cidr).copied().unwrap_or(0)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: RoutingTable> RoutingTable for RefCountedRoutingTable<T> {
+    fn add_cidr(&mut self, cidr: Ipv4Cidr) {
+        let count = self.counts.entry(cidr).or_insert(0);
+        if *count == 0 {
+            self.inner.add_cidr(cidr);
+        }
+
+        *count += 1;
+    }
+
+    fn remove_cidr(&mut self, cidr: Ipv4Cidr) {
+        if let Some(count) = self.counts.get_mut(&cidr) {
+            *count -= 1;
+
+            if *count == 0 {
+                self.counts.remove(&cidr);
+                self.inner.remove_cidr(cidr);
+            }
+        }
+    }
+
+    // Each entry withdraws one reference, so only cidrs whose count drops to zero are removed
+    // from the inner table and counted.
+    fn remove_cidrs(&mut self, cidrs: impl IntoIterator<Item = Ipv4Cidr>) -> usize {
+        let mut released = Vec::new();
+        for cidr in cidrs {
+            if let Some(count) = self.counts.get_mut(&cidr) {
+                *count -= 1;
+
+                if *count == 0 {
+                    self.counts.remove(&cidr);
+                    released.push(cidr);
+                }
+            }
+        }
+
+        self.inner.remove_cidrs(released)
+    }
+
+    // Draining withdraws a cidr for every source at once.
+    fn drain_matching(&mut self, f: &mut dyn FnMut(Ipv4Cidr) -> bool) -> Vec<Ipv4Cidr> {
+        let drained = self.inner.drain_matching(f);
+
+        for cidr in &drained {
+            self.counts.remove(cidr);
+        }
+
+        drained
+    }
+
+    fn remove_within(&mut self, scope: Ipv4Cidr) -> Vec<Ipv4Cidr> {
+        let removed = self.inner.remove_within(scope);
+
+        for cidr in &removed {
+            self.counts.remove(cidr);
+        }
+
+        removed
+    }
+
+    fn find_exact_cidr(&self, addr: Ipv4Addr) -> Option<Ipv4Cidr> {
+        self.inner.find_exact_cidr(addr)
+    }
+
+    fn get(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.inner.get(cidr)
+    }
+
+    fn find_covering(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.inner.find_covering(cidr)
+    }
+
+    fn find_parent(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.inner.find_parent(cidr)
+    }
+
+    fn match_path(&self, addr: Ipv4Addr) -> Vec<Ipv4Cidr> {
+        self.inner.match_path(addr)
+    }
+
+    fn hierarchy_neighbors(&self, cidr: Ipv4Cidr) -> (Option<Ipv4Cidr>, Vec<Ipv4Cidr>) {
+        self.inner.hierarchy_neighbors(cidr)
+    }
+
+    fn size(&self) -> usize {
+        self.inner.size()
+    }
+
+    fn approx_heap_bytes(&self) -> usize {
+        self.inner.approx_heap_bytes()
+            + self.counts.capacity() * (size_of::<(Ipv4Cidr, usize)>() + 1)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Ipv4Cidr> + '_> {
+        self.inner.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RefCountedRoutingTable;
+    use crate::{
+        routing_table::tests::{complex_test, remove_cidrs_test, remove_within_test},
+        HashRoutingTable, Ipv4Cidr, RoutingTable, TrieRoutingTable,
+    };
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_ref_counted_complex() {
+        complex_test(Box::new(RefCountedRoutingTable::new(
+            HashRoutingTable::new(),
+        )));
+    }
+
+    #[test]
+    fn test_ref_counted_remove_within() {
+        remove_within_test(Box::new(RefCountedRoutingTable::new(
+            TrieRoutingTable::new(),
+        )));
+    }
+
+    #[test]
+    fn test_ref_counted_remove_cidrs() {
+        remove_cidrs_test(RefCountedRoutingTable::new(TrieRoutingTable::new()));
+
+        let mut routing_table = RefCountedRoutingTable::new(TrieRoutingTable::new());
+        let shared = Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap();
+        let single = Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap();
+        routing_table.add_cidr(shared);
+        routing_table.add_cidr(shared);
+        routing_table.add_cidr(single);
+
+        assert_eq!(routing_table.remove_cidrs([shared]), 0);
+        assert_eq!(1, routing_table.ref_count(shared));
+        assert_eq!(routing_table.get(shared), Some(shared));
+
+        assert_eq!(routing_table.remove_cidrs([shared, single]), 2);
+        assert_eq!(0, routing_table.size());
+    }
+
+    #[test]
+    fn test_ref_counted_duplicate_adds() {
+        let mut routing_table = RefCountedRoutingTable::new(TrieRoutingTable::new());
+        let cidr = Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap();
+        let addr = Ipv4Addr::new(10, 1, 2, 3);
+
+        routing_table.add_cidr(cidr);
+        routing_table.add_cidr(cidr);
+        assert_eq!(2, routing_table.ref_count(cidr));
+        assert_eq!(1, routing_table.size());
+
+        routing_table.remove_cidr(cidr);
+        assert_eq!(1, routing_table.ref_count(cidr));
+        assert_eq!(routing_table.find_exact_cidr(addr), Some(cidr));
+
+        routing_table.remove_cidr(cidr);
+        assert_eq!(0, routing_table.ref_count(cidr));
+        assert_eq!(routing_table.find_exact_cidr(addr), None);
+        assert_eq!(0, routing_table.size());
+
+        routing_table.remove_cidr(cidr);
+        routing_table.add_cidr(cidr);
+        assert_eq!(1, routing_table.ref_count(cidr));
+        assert_eq!(routing_table.find_exact_cidr(addr), Some(cidr));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{utils::get_cidr_mask, Ipv4Cidr};
+use std::{collections::HashMap, net::Ipv4Addr};
+
+pub struct RoutingTableMap<V> {
+    values: Vec<HashMap<u32, V>>,
+}
+
+impl<V> RoutingTableMap<V> {
+    pub fn new() -> Self {
+        let mut values = Vec::with_capacity(33);
+        for _ in 0..=32 {
+            values.push(HashMap::new());
+        }
+
+        Self { values }
+    }
+
+    pub fn insert(&mut self, cidr: Ipv4Cidr, value: V) -> Option<V> {
+        self.values[cidr.prefix_len() as usize].insert(u32::from(cidr.min()), value)
+    }
+
+    pub fn remove(&mut self, cidr: Ipv4Cidr) -> Option<V> {
+        self.values[cidr.prefix_len() as usize].remove(&u32::from(cidr.min()))
+    }
+
+    pub fn get_value(&self, cidr: Ipv4Cidr) -> Option<&V> {
+        self.values[cidr.prefix_len() as usize].get(&u32::from(cidr.min()))
+    }
+
+    pub fn find_exact(&self, addr: Ipv4Addr) -> Option<(Ipv4Cidr, &V)> {
+        let addr_bits = u32::from(addr);
+
+        (0..=32).rev().find_map(|len| {
+            let bits = addr_bits & get_cidr_mask(len).expect("Len should always be valid.");
+
+            self.values[len as usize].get(&bits).map(|value| {
+                let cidr = Ipv4Cidr::from_bits(bits, len)
+                    .expect("Len and Ipv4Addr should always be valid.");
+                (cidr, value)
+            })
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.iter().map(|bucket| bucket.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.iter().all(|bucket| bucket.is_empty())
+    }
+}
+
+impl<V> RoutingTableMap<Vec<V>> {
+    pub fn push(&mut self, cidr: Ipv4Cidr, value: V) {
+        self.values[cidr.prefix_len() as usize]
+            .entry(u32::from(cidr.min()))
+            .or_default()
+            .push(value);
+    }
+
+    pub fn longest_prefix_values(&self, addr: Ipv4Addr) -> Option<(Ipv4Cidr, &[V])> {
+        self.find_exact(addr)
+            .map(|(cidr, values)| (cidr, values.as_slice()))
+    }
+}
+
+impl<V> Default for RoutingTableMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RoutingTableMap;
+    use crate::Ipv4Cidr;
+    use std::{net::Ipv4Addr, str::FromStr};
+
+    #[test]
+    fn test_map_get_value() {
+        let mut map = RoutingTableMap::new();
+        let cidr = Ipv4Cidr::from_str("10.0.0.0/8").unwrap();
+        let host = Ipv4Cidr::new_host(Ipv4Addr::new(10, 0, 0, 1));
+
+        assert_eq!(map.insert(cidr, "eth0"), None);
+        assert_eq!(map.insert(host, "lo"), None);
+        assert_eq!(map.insert(cidr, "eth1"), Some("eth0"));
+
+        assert_eq!(map.get_value(cidr), Some(&"eth1"));
+        assert_eq!(map.get_value(host), Some(&"lo"));
+        assert_eq!(
+            map.get_value(Ipv4Cidr::from_str("10.0.0.0/9").unwrap()),
+            None
+        );
+        assert_eq!(
+            map.get_value(Ipv4Cidr::new_host(Ipv4Addr::new(10, 0, 0, 2))),
+            None
+        );
+        assert_eq!(2, map.len());
+    }
+
+    #[test]
+    fn test_map_remove() {
+        let mut map = RoutingTableMap::new();
+        let cidr = Ipv4Cidr::from_str("10.0.0.0/8").unwrap();
+        let subnet = Ipv4Cidr::from_str("10.0.0.0/16").unwrap();
+
+        map.insert(cidr, "eth0");
+        map.insert(subnet, "eth1");
+
+        assert_eq!(map.remove(cidr), Some("eth0"));
+        assert_eq!(map.remove(cidr), None);
+        assert_eq!(map.get_value(cidr), None);
+        assert_eq!(map.get_value(subnet), Some(&"eth1"));
+        assert_eq!(1, map.len());
+    }
+
+    #[test]
+    fn test_map_find_exact() {
+        let mut map = RoutingTableMap::new();
+        let default_route = Ipv4Cidr::from_str("0.0.0.0/0").unwrap();
+        let cidr = Ipv4Cidr::from_str("192.168.0.0/16").unwrap();
+
+        assert!(map.is_empty());
+        assert_eq!(map.find_exact(Ipv4Addr::new(192, 168, 1, 1)), None);
+
+        map.insert(default_route, 1);
+        map.insert(cidr, 2);
+
+        assert_eq!(
+            map.find_exact(Ipv4Addr::new(192, 168, 1, 1)),
+            Some((cidr, &2))
+        );
+        assert_eq!(
+            map.find_exact(Ipv4Addr::new(10, 0, 0, 1)),
+            Some((default_route, &1))
+        );
+    }
+
+    #[test]
+    fn test_map_longest_prefix_values() {
+        let mut map = RoutingTableMap::new();
+        let aggregate = Ipv4Cidr::from_str("10.0.0.0/8").unwrap();
+        let cidr = Ipv4Cidr::from_str("10.1.2.0/24").unwrap();
+        let next_hops = [Ipv4Addr::new(172, 16, 0, 1), Ipv4Addr::new(172, 16, 0, 2)];
+
+        map.push(aggregate, Ipv4Addr::new(172, 16, 0, 254));
+        map.push(cidr, next_hops[0]);
+        map.push(cidr, next_hops[1]);
+
+        assert_eq!(
+            map.longest_prefix_values(Ipv4Addr::new(10, 1, 2, 3)),
+            Some((cidr, &next_hops[..]))
+        );
+        assert_eq!(
+            map.longest_prefix_values(Ipv4Addr::new(10, 1, 3, 3)),
+            Some((aggregate, &[Ipv4Addr::new(172, 16, 0, 254)][..]))
+        );
+        assert_eq!(map.longest_prefix_values(Ipv4Addr::new(11, 0, 0, 1)), None);
+        assert_eq!(2, map.len());
+    }
+}
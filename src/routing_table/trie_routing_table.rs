@@ -1,6 +1,10 @@
 use std::{net::Ipv4Addr, ptr};
 
-use crate::{utils::MAX_LENGTH, Ipv4Cidr, RoutingTable};
+use crate::{
+    errors::NetworkParseError,
+    utils::{get_cidr_mask, MAX_LENGTH},
+    Ipv4Cidr, RoutingTable,
+};
 
 #[derive(Clone)]
 struct TrieNode {
@@ -39,6 +43,21 @@ impl TrieNode {
     }
 }
 
+// Every non-null child pointer comes from `Box::into_raw` and is owned by exactly one
+// parent, so it stays valid for as long as the node holding it. The caller must not
+// hold another reference to the same node while a `node_mut` borrow is alive.
+#[inline]
+unsafe fn node_ref<'a>(node: *const TrieNode) -> &'a TrieNode {
+    debug_assert!(!node.is_null());
+    unsafe { &*node }
+}
+
+#[inline]
+unsafe fn node_mut<'a>(node: *mut TrieNode) -> &'a mut TrieNode {
+    debug_assert!(!node.is_null());
+    unsafe { &mut *node }
+}
+
 impl Drop for TrieNode {
     fn drop(&mut self) {
         for child in self.children {
@@ -52,6 +71,11 @@ impl Drop for TrieNode {
 pub struct TrieRoutingTable {
     root: TrieNode,
     size: usize,
+    // Stored leaves per prefix length, so `max_depth` can be lowered without walking the trie.
+    leaf_counts: [usize; 33],
+    // Length of the longest stored prefix, 0 when empty.
+    max_depth: u8,
+    disjoint: bool,
 }
 
 impl TrieRoutingTable {
@@ -59,85 +83,609 @@ impl TrieRoutingTable {
         Self {
             root: TrieNode::new(false),
             size: 0,
+            leaf_counts: [0; 33],
+            max_depth: 0,
+            disjoint: false,
+        }
+    }
+
+    // Every stored cidr must be neither an ancestor nor a descendant of another one;
+    // `add_cidr` panics on a violation, `try_add_cidr` reports it instead.
+    pub fn new_disjoint() -> Self {
+        Self {
+            disjoint: true,
+            ..Self::new()
+        }
+    }
+
+    pub fn try_add_cidr(&mut self, cidr: Ipv4Cidr) -> Result<(), NetworkParseError> {
+        if self.disjoint && self.overlaps_stored(cidr) {
+            return Err(NetworkParseError::OverlappingCidr);
+        }
+
+        self.add_cidr(cidr);
+        Ok(())
+    }
+
+    // Network-ordered insertion walks the same upper nodes back to back, which keeps them
+    // hot in cache while the trie is being built.
+    pub fn load_sorted(cidrs: &mut [Ipv4Cidr]) -> Self {
+        cidrs.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+
+        let mut routing_table = Self::new();
+        for &cidr in cidrs.iter() {
+            routing_table.add_cidr(cidr);
+        }
+
+        routing_table
+    }
+
+    // A pre-order walk reaches every leaf in network-then-length order.
+    pub fn into_sorted_vec(self) -> Vec<Ipv4Cidr> {
+        let mut leaves = Vec::with_capacity(self.size);
+        Self::collect_leaves(&self.root, 0, 0, &mut leaves);
+
+        leaves
+    }
+
+    pub fn max_depth(&self) -> u8 {
+        self.max_depth
+    }
+
+    pub fn node_count(&self) -> usize {
+        Self::count_nodes(&self.root)
+    }
+
+    pub fn compact(&mut self) {
+        let mut leaves = Vec::with_capacity(self.size);
+        Self::collect_leaves(&self.root, 0, 0, &mut leaves);
+
+        let mut compacted = Self {
+            disjoint: self.disjoint,
+            ..Self::new()
+        };
+        for cidr in leaves {
+            compacted.add_cidr(cidr);
         }
+
+        *self = compacted;
+    }
+
+    // `Ipv4Cidr` already bounds its length, so this only trips if that invariant breaks;
+    // without it the descent would shift past the address width.
+    #[inline]
+    fn checked_depth(len: u8) -> u8 {
+        if len > MAX_LENGTH {
+            Self::depth_overflow(len);
+        }
+
+        len
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn depth_overflow(len: u8) -> ! {
+        panic!("prefix length {len} exceeds the trie depth of {MAX_LENGTH}")
     }
 
     #[inline]
     fn take_bit(&self, bit_addr: u32, r_idx: u8) -> u32 {
         (bit_addr >> (MAX_LENGTH - r_idx)) & 1
     }
+
+    #[inline]
+    fn find_longest(&self, bit_addr: u32, max_len: u8) -> Option<Ipv4Cidr> {
+        let mut best_len = if self.root.is_leaf { 0 } else { u8::MAX };
+        let mut node: *const TrieNode = &self.root;
+
+        for len in 1..=max_len {
+            let bit = self.take_bit(bit_addr, len);
+
+            node = unsafe { node_ref(node).get(bit as usize) };
+            if node.is_null() {
+                break;
+            }
+
+            if unsafe { node_ref(node).is_leaf } {
+                best_len = len;
+            }
+        }
+
+        if best_len == u8::MAX {
+            Option::None
+        } else {
+            let truncated_addr = if best_len == 0 {
+                0
+            } else {
+                bit_addr & !((1 << (MAX_LENGTH - best_len)) - 1)
+            };
+            Option::Some(Ipv4Cidr::new(Ipv4Addr::from(truncated_addr), best_len).unwrap())
+        }
+    }
+
+    fn overlaps_stored(&self, cidr: Ipv4Cidr) -> bool {
+        if self.get(cidr).is_some() {
+            return false;
+        }
+
+        self.find_covering(cidr).is_some() || self.find_node(cidr).is_some_and(Self::has_leaf_below)
+    }
+
+    fn has_leaf_below(node: &TrieNode) -> bool {
+        node.children.into_iter().any(|child| {
+            !child.is_null() && {
+                let child = unsafe { node_ref(child) };
+                child.is_leaf || Self::has_leaf_below(child)
+            }
+        })
+    }
+
+    fn count_leaves(node: &TrieNode) -> usize {
+        let mut count = usize::from(node.is_leaf);
+
+        for child in node.children {
+            if !child.is_null() {
+                count += Self::count_leaves(unsafe { node_ref(child) });
+            }
+        }
+
+        count
+    }
+
+    fn forget_leaf(&mut self, len: u8) {
+        self.size -= 1;
+        self.leaf_counts[len as usize] -= 1;
+    }
+
+    // Only ever moves down from the current maximum, so this is at most 33 steps.
+    fn lower_max_depth(&mut self) {
+        while self.max_depth > 0 && self.leaf_counts[self.max_depth as usize] == 0 {
+            self.max_depth -= 1;
+        }
+    }
+
+    fn count_nodes(node: &TrieNode) -> usize {
+        let mut count = 1;
+
+        for child in node.children {
+            if !child.is_null() {
+                count += Self::count_nodes(unsafe { node_ref(child) });
+            }
+        }
+
+        count
+    }
+
+    fn find_node(&self, cidr: Ipv4Cidr) -> Option<&TrieNode> {
+        let bit_addr = u32::from(cidr.min());
+        let mut node: *const TrieNode = &self.root;
+
+        for len in 1..=cidr.prefix_len() {
+            let bit = self.take_bit(bit_addr, len);
+            node = unsafe { node_ref(node).get(bit as usize) };
+
+            if node.is_null() {
+                return None;
+            }
+        }
+
+        Some(unsafe { node_ref(node) })
+    }
+
+    // Collects the first leaf found on every path below `node`, without descending past it.
+    fn collect_top_leaves(node: &TrieNode, bits: u32, len: u8, leaves: &mut Vec<Ipv4Cidr>) {
+        for (bit, child) in node.children.into_iter().enumerate() {
+            if child.is_null() {
+                continue;
+            }
+
+            let child = unsafe { node_ref(child) };
+            let child_bits = bits | ((bit as u32) << (MAX_LENGTH - len - 1));
+
+            if child.is_leaf {
+                let cidr =
+                    Ipv4Cidr::from_bits(child_bits, len + 1).expect("Trie depth never exceeds 32.");
+                leaves.push(cidr);
+            } else {
+                Self::collect_top_leaves(child, child_bits, len + 1, leaves);
+            }
+        }
+    }
+
+    // Subtrees whose address block lies outside `[start, end]` are skipped entirely.
+    fn collect_leaves_in_range(
+        node: &TrieNode,
+        bits: u32,
+        len: u8,
+        (start, end): (u32, u32),
+        leaves: &mut Vec<Ipv4Cidr>,
+    ) {
+        let last = bits | !get_cidr_mask(len).expect("Trie depth never exceeds 32.");
+        if last < start || end < bits {
+            return;
+        }
+
+        if node.is_leaf {
+            let cidr = Ipv4Cidr::from_bits(bits, len).expect("Trie depth never exceeds 32.");
+            leaves.push(cidr);
+        }
+
+        for (bit, child) in node.children.into_iter().enumerate() {
+            if !child.is_null() {
+                let child_bits = bits | ((bit as u32) << (MAX_LENGTH - len - 1));
+                Self::collect_leaves_in_range(
+                    unsafe { node_ref(child) },
+                    child_bits,
+                    len + 1,
+                    (start, end),
+                    leaves,
+                );
+            }
+        }
+    }
+
+    fn collect_leaves_at_depth(
+        node: &TrieNode,
+        bits: u32,
+        len: u8,
+        depth: u8,
+        leaves: &mut Vec<Ipv4Cidr>,
+    ) {
+        if len == depth {
+            if node.is_leaf {
+                let cidr = Ipv4Cidr::from_bits(bits, len).expect("Trie depth never exceeds 32.");
+                leaves.push(cidr);
+            }
+
+            return;
+        }
+
+        for (bit, child) in node.children.into_iter().enumerate() {
+            if !child.is_null() {
+                let child_bits = bits | ((bit as u32) << (MAX_LENGTH - len - 1));
+                Self::collect_leaves_at_depth(
+                    unsafe { node_ref(child) },
+                    child_bits,
+                    len + 1,
+                    depth,
+                    leaves,
+                );
+            }
+        }
+    }
+
+    fn collect_leaves_with_depth(
+        node: &TrieNode,
+        bits: u32,
+        len: u8,
+        mut ancestors: usize,
+        leaves: &mut Vec<(Ipv4Cidr, usize)>,
+    ) {
+        if node.is_leaf {
+            let cidr = Ipv4Cidr::from_bits(bits, len).expect("Trie depth never exceeds 32.");
+            leaves.push((cidr, ancestors));
+            ancestors += 1;
+        }
+
+        for (bit, child) in node.children.into_iter().enumerate() {
+            if !child.is_null() {
+                let child_bits = bits | ((bit as u32) << (MAX_LENGTH - len - 1));
+                Self::collect_leaves_with_depth(
+                    unsafe { node_ref(child) },
+                    child_bits,
+                    len + 1,
+                    ancestors,
+                    leaves,
+                );
+            }
+        }
+    }
+
+    fn collect_leaves(node: &TrieNode, bits: u32, len: u8, leaves: &mut Vec<Ipv4Cidr>) {
+        if node.is_leaf {
+            let cidr = Ipv4Cidr::from_bits(bits, len).expect("Trie depth never exceeds 32.");
+            leaves.push(cidr);
+        }
+
+        for (bit, child) in node.children.into_iter().enumerate() {
+            if !child.is_null() {
+                let child_bits = bits | ((bit as u32) << (MAX_LENGTH - len - 1));
+                Self::collect_leaves(unsafe { node_ref(child) }, child_bits, len + 1, leaves);
+            }
+        }
+    }
+}
+
+impl Default for TrieRoutingTable {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl RoutingTable for TrieRoutingTable {
     fn add_cidr(&mut self, cidr: Ipv4Cidr) {
+        assert!(
+            !(self.disjoint && self.overlaps_stored(cidr)),
+            "{cidr} overlaps a cidr already stored in a disjoint table"
+        );
+
+        let depth = Self::checked_depth(cidr.prefix_len());
         let bit_addr = u32::from(cidr.min());
         let mut node: *mut TrieNode = &mut self.root;
 
-        for len in 1..=cidr.prefix_len() {
+        for len in 1..=depth {
             let bit = self.take_bit(bit_addr, len);
-            node = unsafe { (*node).get_or_add(bit as usize) };
+            node = unsafe { node_mut(node).get_or_add(bit as usize) };
         }
 
-        self.size += 1;
-        unsafe { (*node).mark_leaf() };
+        if unsafe { !node_ref(node).is_leaf } {
+            self.size += 1;
+            self.leaf_counts[depth as usize] += 1;
+            self.max_depth = self.max_depth.max(depth);
+            unsafe { node_mut(node).mark_leaf() };
+        }
     }
 
     fn remove_cidr(&mut self, cidr: Ipv4Cidr) {
+        let depth = Self::checked_depth(cidr.prefix_len());
         let bit_addr = u32::from(cidr.min());
         let mut node: *mut TrieNode = &mut self.root;
 
-        for len in 1..=cidr.prefix_len() {
+        for len in 1..=depth {
             let bit = self.take_bit(bit_addr, len);
-            node = unsafe { (*node).get(bit as usize) };
+            node = unsafe { node_ref(node).get(bit as usize) };
 
             if node.is_null() {
                 return;
             }
         }
 
-        self.size -= 1;
-        unsafe { (*node).unmark_leaf() };
+        if unsafe { node_ref(node).is_leaf } {
+            unsafe { node_mut(node).unmark_leaf() };
+            self.forget_leaf(depth);
+            self.lower_max_depth();
+        }
+    }
+
+    // Sorted so that neighbouring removals walk shared paths back to back; the maximum
+    // depth is settled once for the whole batch.
+    fn remove_cidrs(&mut self, cidrs: impl IntoIterator<Item = Ipv4Cidr>) -> usize {
+        let mut targets: Vec<Ipv4Cidr> = cidrs.into_iter().collect();
+        targets.sort_unstable_by_key(|cidr| (cidr.min(), cidr.prefix_len()));
+        targets.dedup();
+
+        let mut removed = 0;
+        'targets: for cidr in targets {
+            let depth = Self::checked_depth(cidr.prefix_len());
+            let bit_addr = u32::from(cidr.min());
+            let mut node: *mut TrieNode = &mut self.root;
+
+            for len in 1..=depth {
+                let bit = self.take_bit(bit_addr, len);
+                node = unsafe { node_ref(node).get(bit as usize) };
+
+                if node.is_null() {
+                    continue 'targets;
+                }
+            }
+
+            if unsafe { node_ref(node).is_leaf } {
+                unsafe { node_mut(node).unmark_leaf() };
+                self.forget_leaf(depth);
+                removed += 1;
+            }
+        }
+
+        self.lower_max_depth();
+        removed
+    }
+
+    fn remove_within(&mut self, scope: Ipv4Cidr) -> Vec<Ipv4Cidr> {
+        let bit_addr = u32::from(scope.min());
+        let mut removed = Vec::new();
+
+        if scope.prefix_len() == 0 {
+            Self::collect_leaves(&self.root, 0, 0, &mut removed);
+            self.root = TrieNode::new(false);
+            self.size = 0;
+            self.leaf_counts = [0; 33];
+            self.max_depth = 0;
+
+            return removed;
+        }
+
+        let mut node: *mut TrieNode = &mut self.root;
+        for len in 1..scope.prefix_len() {
+            let bit = self.take_bit(bit_addr, len);
+            node = unsafe { node_ref(node).get(bit as usize) };
+
+            if node.is_null() {
+                return removed;
+            }
+        }
+
+        let bit = self.take_bit(bit_addr, scope.prefix_len()) as usize;
+        let subtree = unsafe { node_ref(node).get(bit) };
+        if subtree.is_null() {
+            return removed;
+        }
+
+        unsafe { node_mut(node).children[bit] = ptr::null_mut() };
+        let subtree = unsafe { Box::from_raw(subtree) };
+        Self::collect_leaves(&subtree, bit_addr, scope.prefix_len(), &mut removed);
+
+        for cidr in removed.iter() {
+            self.forget_leaf(cidr.prefix_len());
+        }
+        self.lower_max_depth();
+
+        removed
     }
 
     fn find_exact_cidr(&self, addr: std::net::Ipv4Addr) -> Option<Ipv4Cidr> {
+        self.find_longest(u32::from(addr), MAX_LENGTH)
+    }
+
+    fn get(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.find_node(cidr)
+            .and_then(|node| node.is_leaf.then_some(cidr))
+    }
+
+    fn is_routable(&self, addr: Ipv4Addr) -> bool {
         let bit_addr = u32::from(addr);
-        let mut best_len = if self.root.is_leaf { 0 } else { u8::MAX };
         let mut node: *const TrieNode = &self.root;
 
+        for len in 1..=MAX_LENGTH {
+            if unsafe { node_ref(node).is_leaf } {
+                return true;
+            }
+
+            let bit = self.take_bit(bit_addr, len);
+            node = unsafe { node_ref(node).get(bit as usize) };
+            if node.is_null() {
+                return false;
+            }
+        }
+
+        unsafe { node_ref(node).is_leaf }
+    }
+
+    fn longest_prefix_match_capped(&self, addr: Ipv4Addr, max_len: u8) -> Option<Ipv4Cidr> {
+        self.find_longest(u32::from(addr), max_len.min(MAX_LENGTH))
+    }
+
+    fn find_covering(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        self.find_longest(u32::from(cidr.min()), cidr.prefix_len())
+    }
+
+    fn find_parent(&self, cidr: Ipv4Cidr) -> Option<Ipv4Cidr> {
+        match cidr.prefix_len() {
+            0 => None,
+            len => self.find_longest(u32::from(cidr.min()), len - 1),
+        }
+    }
+
+    fn match_path(&self, addr: Ipv4Addr) -> Vec<Ipv4Cidr> {
+        let bit_addr = u32::from(addr);
+        let mut path = Vec::new();
+        let mut node: *const TrieNode = &self.root;
+
+        if self.root.is_leaf {
+            path.push(Ipv4Cidr::from_bits(0, 0).expect("Default route should always be valid."));
+        }
+
         for len in 1..=MAX_LENGTH {
             let bit = self.take_bit(bit_addr, len);
 
-            node = unsafe { (*node).get(bit as usize) };
+            node = unsafe { node_ref(node).get(bit as usize) };
             if node.is_null() {
                 break;
             }
 
-            if unsafe { (*node).is_leaf } {
-                best_len = len;
+            if unsafe { node_ref(node).is_leaf } {
+                let bits = bit_addr & get_cidr_mask(len).expect("Len should always be valid.");
+                path.push(Ipv4Cidr::from_bits(bits, len).expect("Trie depth never exceeds 32."));
             }
         }
 
-        if best_len == u8::MAX {
-            Option::None
-        } else {
-            let truncated_addr = if best_len == 0 {
-                0
-            } else {
-                bit_addr & !((1 << (MAX_LENGTH - best_len)) - 1)
-            };
-            Option::Some(Ipv4Cidr::new(Ipv4Addr::from(truncated_addr), best_len).unwrap())
+        path
+    }
+
+    fn hierarchy_neighbors(&self, cidr: Ipv4Cidr) -> (Option<Ipv4Cidr>, Vec<Ipv4Cidr>) {
+        let mut children = Vec::new();
+        if let Some(node) = self.find_node(cidr) {
+            Self::collect_top_leaves(
+                node,
+                u32::from(cidr.min()),
+                cidr.prefix_len(),
+                &mut children,
+            );
         }
+
+        (self.find_parent(cidr), children)
+    }
+
+    fn routes_in_range(&self, start: Ipv4Addr, end: Ipv4Addr) -> Vec<Ipv4Cidr> {
+        let mut routes = Vec::new();
+        if start > end {
+            return routes;
+        }
+
+        Self::collect_leaves_in_range(
+            &self.root,
+            0,
+            0,
+            (u32::from(start), u32::from(end)),
+            &mut routes,
+        );
+
+        routes
+    }
+
+    fn count_within(&self, scope: Ipv4Cidr) -> usize {
+        self.find_node(scope).map_or(0, |node| {
+            Self::count_leaves(node) - usize::from(node.is_leaf)
+        })
+    }
+
+    fn iter_with_depth(&self) -> Vec<(Ipv4Cidr, usize)> {
+        let mut leaves = Vec::with_capacity(self.size);
+        Self::collect_leaves_with_depth(&self.root, 0, 0, 0, &mut leaves);
+
+        leaves
+    }
+
+    fn routes_of_length(&self, len: u8) -> Vec<Ipv4Cidr> {
+        let mut routes = Vec::new();
+        if len <= MAX_LENGTH {
+            Self::collect_leaves_at_depth(&self.root, 0, 0, len, &mut routes);
+        }
+
+        routes
     }
 
     fn size(&self) -> usize {
         self.size
     }
+
+    fn approx_heap_bytes(&self) -> usize {
+        self.node_count() * size_of::<TrieNode>()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Ipv4Cidr> + '_> {
+        Box::new(self.cidrs_sorted().into_iter())
+    }
+
+    fn cidrs_sorted(&self) -> Vec<Ipv4Cidr> {
+        let mut leaves = Vec::with_capacity(self.size);
+        Self::collect_leaves(&self.root, 0, 0, &mut leaves);
+
+        leaves
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::TrieRoutingTable;
-    use crate::routing_table::tests::{complex_test, empty_test, one_global_cidr, simple_test};
+    use crate::{
+        errors::NetworkParseError,
+        routing_table::tests::{
+            add_cidr_max_len_test, aggregated_test, aggregates_exceeding_test,
+            approx_heap_bytes_test, best_fit_test, cidrs_sorted_test, classify_address_test,
+            complement_within_test, complex_test, count_within_test, coverage_test,
+            covered_addresses_test, covers_test, default_free_test, drain_matching_test,
+            duplicate_add_test, empty_test, filter_routable_test, find_by_range_test,
+            find_parent_test, fingerprint_test, get_test, has_same_cidrs_test,
+            hierarchy_neighbors_test, is_routable_test, iter_with_depth_test, leaf_pairs_be_test,
+            leaf_pairs_test, longest_prefix_match_capped_test, longest_prefix_match_detailed_test,
+            lookup_or_default_test, match_as_tcam_test, match_path_test, match_with_backup_test,
+            next_free_subnet_test, one_global_cidr, remove_cidrs_test, remove_within_test,
+            routes_in_range_test, routes_of_length_test, simple_test, sorted_cidrs, to_fib_24_test,
+            unsorted_cidrs,
+        },
+        Ipv4Cidr, ListRoutingTable, RoutingTable,
+    };
+    use std::{net::Ipv4Addr, time::Instant};
 
     #[test]
     fn test_hash_empty_case() {
@@ -158,4 +706,482 @@ mod tests {
     fn test_hash_complex() {
         complex_test(Box::new(TrieRoutingTable::new()))
     }
+
+    #[test]
+    fn test_compact_after_churn() {
+        let mut routing_table = TrieRoutingTable::new();
+        let kept = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new_host(Ipv4Addr::new(127, 0, 0, 1)),
+        ];
+
+        for cidr in kept {
+            routing_table.add_cidr(cidr);
+        }
+
+        for round in 0..50u8 {
+            let cidr = Ipv4Cidr::new(Ipv4Addr::new(172, 16, round, 0), 24).unwrap();
+            routing_table.add_cidr(cidr);
+            routing_table.remove_cidr(cidr);
+        }
+
+        let nodes_before = routing_table.node_count();
+        routing_table.compact();
+
+        assert!(
+            routing_table.node_count() < nodes_before,
+            "we expect compaction to release nodes left behind by removed cidrs"
+        );
+        assert_eq!(kept.len(), routing_table.size());
+
+        let test_cases = [
+            (Ipv4Addr::new(10, 1, 2, 3), Some(kept[0])),
+            (Ipv4Addr::new(192, 168, 5, 5), Some(kept[1])),
+            (Ipv4Addr::new(127, 0, 0, 1), Some(kept[2])),
+            (Ipv4Addr::new(127, 0, 0, 2), None),
+            (Ipv4Addr::new(172, 16, 3, 1), None),
+        ];
+
+        for (addr, expected) in test_cases {
+            let result = routing_table.find_exact_cidr(addr);
+
+            assert_eq!(
+                result, expected,
+                "we find {addr} inside compacted table and expect result to be {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_disjoint_rejects_nested() {
+        let mut routing_table = TrieRoutingTable::new_disjoint();
+        let cidr = Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 24).unwrap();
+        let sibling = Ipv4Cidr::new(Ipv4Addr::new(10, 1, 3, 0), 24).unwrap();
+
+        assert_eq!(routing_table.try_add_cidr(cidr), Ok(()));
+
+        let test_cases = [
+            (
+                Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap(),
+                false,
+            ),
+            (
+                Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 128), 25).unwrap(),
+                false,
+            ),
+            (Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap(), false),
+            (Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 2, 3)), false),
+            (cidr, true),
+            (sibling, true),
+        ];
+
+        for (candidate, accepted) in test_cases {
+            let expected = if accepted {
+                Ok(())
+            } else {
+                Err(NetworkParseError::OverlappingCidr)
+            };
+
+            assert_eq!(
+                routing_table.try_add_cidr(candidate),
+                expected,
+                "we expect adding {candidate} next to {cidr} to give {expected:?}"
+            );
+        }
+
+        assert_eq!(2, routing_table.size());
+        assert_eq!(
+            routing_table.find_exact_cidr(Ipv4Addr::new(10, 1, 2, 200)),
+            Some(cidr)
+        );
+
+        routing_table.remove_cidr(cidr);
+        assert_eq!(
+            routing_table.try_add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 128), 25).unwrap()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "overlaps a cidr already stored in a disjoint table")]
+    fn test_disjoint_add_cidr_panics() {
+        let mut routing_table = TrieRoutingTable::new_disjoint();
+
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 24).unwrap());
+        routing_table.add_cidr(Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap());
+    }
+
+    // Guards against the trie degrading to a linear scan. The budget is only "not slower
+    // than the list", which leaves a wide margin on any machine, but it needs a large table
+    // to mean anything, so it is opt-in.
+    #[test]
+    #[ignore]
+    fn test_trie_lookup_outpaces_list() {
+        let mut seed = 0x9e37_79b9u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            seed
+        };
+
+        let cidrs: Vec<Ipv4Cidr> = (0..20_000)
+            .map(|_| {
+                let bits = next();
+                Ipv4Cidr::containing(Ipv4Addr::from(bits), 8 + (bits % 25) as u8).unwrap()
+            })
+            .collect();
+        let addrs: Vec<Ipv4Addr> = (0..5_000).map(|_| Ipv4Addr::from(next())).collect();
+
+        let mut trie = TrieRoutingTable::new();
+        let mut list = ListRoutingTable::new();
+        for &cidr in cidrs.iter() {
+            trie.add_cidr(cidr);
+            list.add_cidr(cidr);
+        }
+
+        let start = Instant::now();
+        let trie_results: Vec<Option<Ipv4Cidr>> = addrs
+            .iter()
+            .map(|&addr| trie.find_exact_cidr(addr))
+            .collect();
+        let trie_elapsed = start.elapsed();
+
+        let start = Instant::now();
+        let list_results: Vec<Option<Ipv4Cidr>> = addrs
+            .iter()
+            .map(|&addr| list.find_exact_cidr(addr))
+            .collect();
+        let list_elapsed = start.elapsed();
+
+        assert_eq!(trie_results, list_results);
+        assert!(
+            trie_elapsed < list_elapsed,
+            "we expect the trie ({trie_elapsed:?}) to be faster than the list ({list_elapsed:?})"
+        );
+    }
+
+    #[test]
+    fn test_checked_depth() {
+        assert_eq!(TrieRoutingTable::checked_depth(0), 0);
+        assert_eq!(TrieRoutingTable::checked_depth(32), 32);
+    }
+
+    #[test]
+    #[should_panic(expected = "prefix length 33 exceeds the trie depth of 32")]
+    fn test_checked_depth_overflow() {
+        TrieRoutingTable::checked_depth(33);
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let mut routing_table = TrieRoutingTable::new();
+        assert_eq!(routing_table.max_depth(), 0);
+
+        let cidrs = [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 128), 25).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(172, 16, 0, 0), 12).unwrap(),
+        ];
+        for cidr in cidrs {
+            routing_table.add_cidr(cidr);
+        }
+        assert_eq!(routing_table.max_depth(), 25);
+
+        routing_table.remove_cidr(cidrs[1]);
+        assert_eq!(routing_table.max_depth(), 25);
+
+        routing_table.remove_cidr(cidrs[3]);
+        assert_eq!(routing_table.max_depth(), 16);
+
+        routing_table.add_cidr(Ipv4Cidr::new_host(Ipv4Addr::new(192, 168, 1, 1)));
+        assert_eq!(routing_table.max_depth(), 32);
+
+        routing_table.remove_within(cidrs[2]);
+        assert_eq!(routing_table.max_depth(), 12);
+
+        routing_table.remove_within(Ipv4Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap());
+        assert_eq!(routing_table.max_depth(), 0);
+    }
+
+    #[test]
+    fn test_max_depth_drops_to_next_longest() {
+        let mut routing_table = TrieRoutingTable::new();
+        let deepest = Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 2, 3));
+        for cidr in [
+            Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(10, 1, 2, 0), 24).unwrap(),
+            Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 24).unwrap(),
+            deepest,
+        ] {
+            routing_table.add_cidr(cidr);
+        }
+        assert_eq!(routing_table.max_depth(), 32);
+
+        routing_table.remove_cidr(Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 2, 4)));
+        assert_eq!(routing_table.max_depth(), 32);
+
+        routing_table.remove_cidr(deepest);
+        assert_eq!(routing_table.max_depth(), 24);
+
+        routing_table.remove_cidr(deepest);
+        assert_eq!(routing_table.max_depth(), 24);
+        assert_eq!(routing_table.size(), 3);
+    }
+
+    #[test]
+    fn test_load_sorted() {
+        let mut seed = 0x2545_f491u32;
+        let mut cidrs: Vec<Ipv4Cidr> = (0..500)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                Ipv4Cidr::containing(Ipv4Addr::from(seed), (seed % 33) as u8).unwrap()
+            })
+            .collect();
+
+        let mut unsorted = TrieRoutingTable::new();
+        for &cidr in cidrs.iter() {
+            unsorted.add_cidr(cidr);
+        }
+        let routing_table = TrieRoutingTable::load_sorted(&mut cidrs);
+
+        assert!(cidrs
+            .windows(2)
+            .all(|pair| (pair[0].min(), pair[0].prefix_len())
+                <= (pair[1].min(), pair[1].prefix_len())));
+        assert_eq!(unsorted.size(), routing_table.size());
+        assert_eq!(unsorted.node_count(), routing_table.node_count());
+        assert_eq!(
+            unsorted.iter().collect::<Vec<_>>(),
+            routing_table.iter().collect::<Vec<_>>()
+        );
+
+        for cidr in cidrs {
+            let addr = cidr.max();
+
+            assert_eq!(
+                routing_table.find_exact_cidr(addr),
+                unsorted.find_exact_cidr(addr),
+                "we expect sorted and unsorted loads to agree on {addr}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_trie_find_parent() {
+        find_parent_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_covers() {
+        covers_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_match_path() {
+        match_path_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_get() {
+        get_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_coverage() {
+        coverage_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_hierarchy_neighbors() {
+        hierarchy_neighbors_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_add_cidr_max_len() {
+        add_cidr_max_len_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_drain_matching() {
+        drain_matching_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_next_free_subnet() {
+        next_free_subnet_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_classify_address() {
+        classify_address_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_remove_within() {
+        remove_within_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_approx_heap_bytes() {
+        approx_heap_bytes_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_fingerprint() {
+        fingerprint_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_duplicate_add() {
+        duplicate_add_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_match_as_tcam() {
+        match_as_tcam_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_best_fit() {
+        best_fit_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_covered_addresses() {
+        covered_addresses_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_is_routable() {
+        is_routable_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_has_same_cidrs() {
+        has_same_cidrs_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_find_by_range() {
+        find_by_range_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_aggregated() {
+        aggregated_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_lookup_or_default() {
+        lookup_or_default_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_leaf_pairs() {
+        leaf_pairs_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_routes_in_range() {
+        routes_in_range_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_routes_of_length() {
+        routes_of_length_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_complement_within() {
+        complement_within_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_iter_with_depth() {
+        iter_with_depth_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "fills and scans a 16M-entry table")]
+    fn test_trie_to_fib_24() {
+        to_fib_24_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_into_sorted_vec() {
+        assert_eq!(TrieRoutingTable::new().into_sorted_vec(), vec![]);
+
+        let mut routing_table = TrieRoutingTable::new();
+        for cidr in unsorted_cidrs() {
+            routing_table.add_cidr(cidr);
+        }
+
+        assert_eq!(routing_table.into_sorted_vec(), sorted_cidrs());
+    }
+
+    #[test]
+    fn test_trie_filter_routable() {
+        filter_routable_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_longest_prefix_match_detailed() {
+        longest_prefix_match_detailed_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_longest_prefix_match_capped() {
+        longest_prefix_match_capped_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_count_within() {
+        count_within_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_leaf_pairs_be() {
+        leaf_pairs_be_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_aggregates_exceeding() {
+        aggregates_exceeding_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_match_with_backup() {
+        match_with_backup_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_cidrs_sorted() {
+        cidrs_sorted_test(Box::new(TrieRoutingTable::new()));
+    }
+
+    #[test]
+    fn test_trie_remove_cidrs() {
+        remove_cidrs_test(TrieRoutingTable::new());
+
+        let aggregate = Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap();
+        let subnet = Ipv4Cidr::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap();
+        let host = Ipv4Cidr::new_host(Ipv4Addr::new(10, 1, 2, 3));
+        let mut routing_table = TrieRoutingTable::new();
+        for cidr in [aggregate, subnet, host] {
+            routing_table.add_cidr(cidr);
+        }
+
+        assert_eq!(routing_table.remove_cidrs([host, subnet, host]), 2);
+        assert_eq!(routing_table.max_depth(), 8);
+        assert_eq!(routing_table.size(), 1);
+        assert_eq!(routing_table.remove_cidrs([aggregate]), 1);
+        assert_eq!(routing_table.max_depth(), 0);
+    }
+
+    #[test]
+    fn test_trie_default_free() {
+        default_free_test(Box::new(TrieRoutingTable::new()));
+    }
 }
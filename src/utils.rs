@@ -1,18 +1,27 @@
-use crate::errors::NetworkParseError;
+use crate::{
+    errors::NetworkParseError,
+    family::{AddrFamily, Ipv4},
+};
 use std::net::Ipv4Addr;
 
-pub const MAX_LENGTH: u8 = 32;
+pub const MAX_LENGTH: u8 = Ipv4::MAX_LENGTH;
 
-pub fn get_cidr_mask(len: u8) -> Result<u32, NetworkParseError> {
-    if len > MAX_LENGTH {
-        Err(NetworkParseError::NetworkLengthError)
-    } else {
-        let right_len = MAX_LENGTH - len;
-        let all_bits = u32::MAX as u64;
-        let mask = (all_bits >> right_len) << right_len;
-
-        Ok(mask as u32)
+pub const MASKS: [u32; 33] = {
+    let mut masks = [0; 33];
+    let mut len = 1;
+    while len <= 32 {
+        masks[len] = u32::MAX << (32 - len);
+        len += 1;
     }
+
+    masks
+};
+
+pub fn get_cidr_mask(len: u8) -> Result<u32, NetworkParseError> {
+    MASKS
+        .get(len as usize)
+        .copied()
+        .ok_or(NetworkParseError::NetworkLengthError)
 }
 
 pub fn cut_addr(addr: Ipv4Addr, len: u8) -> Result<Ipv4Addr, NetworkParseError> {
@@ -31,11 +40,36 @@ pub fn cut_addr(addr: Ipv4Addr, len: u8) -> Result<Ipv4Addr, NetworkParseError>
     }
 }
 
+pub fn divergence_prefix_len(a: Ipv4Addr, b: Ipv4Addr) -> u8 {
+    (u32::from(a) ^ u32::from(b)).leading_zeros() as u8
+}
+
+pub fn merge_ranges(mut ranges: Vec<(Ipv4Addr, Ipv4Addr)>) -> Vec<(Ipv4Addr, Ipv4Addr)> {
+    ranges.sort_unstable();
+
+    let mut merged: Vec<(Ipv4Addr, Ipv4Addr)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some((_, last_end))
+                if u64::from(u32::from(start)) <= u64::from(u32::from(*last_end)) + 1 =>
+            {
+                *last_end = (*last_end).max(end);
+            }
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
 #[cfg(test)]
 mod tests {
     use std::net::Ipv4Addr;
 
-    use super::{cut_addr, get_cidr_mask, NetworkParseError};
+    use super::{
+        cut_addr, divergence_prefix_len, get_cidr_mask, merge_ranges, NetworkParseError, MASKS,
+        MAX_LENGTH,
+    };
 
     #[test]
     fn test_get_valid_cidr_mask() {
@@ -60,6 +94,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_masks_table() {
+        for len in 0..=MAX_LENGTH {
+            let right_len = MAX_LENGTH - len;
+            let computed = ((u32::MAX as u64 >> right_len) << right_len) as u32;
+
+            assert_eq!(
+                MASKS[len as usize], computed,
+                "we expect precomputed /{len} mask to match the shifted one"
+            );
+        }
+    }
+
     #[test]
     fn test_get_invalid_cidr_mask() {
         let test_cases = [33, 34, 35, 50, 100];
@@ -115,4 +162,57 @@ mod tests {
             assert_eq!(actual, Err(NetworkParseError::NetworkLengthError));
         }
     }
+
+    #[test]
+    fn test_divergence_prefix_len() {
+        let test_cases = [
+            (Ipv4Addr::new(10, 1, 2, 3), Ipv4Addr::new(10, 1, 2, 3), 32),
+            (Ipv4Addr::new(0, 0, 0, 0), Ipv4Addr::new(128, 0, 0, 0), 0),
+            (Ipv4Addr::new(127, 0, 0, 1), Ipv4Addr::new(255, 0, 0, 1), 0),
+            (Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(10, 128, 0, 0), 8),
+            (
+                Ipv4Addr::new(192, 168, 1, 0),
+                Ipv4Addr::new(192, 168, 2, 0),
+                22,
+            ),
+            (
+                Ipv4Addr::new(192, 168, 1, 4),
+                Ipv4Addr::new(192, 168, 1, 5),
+                31,
+            ),
+        ];
+
+        for (a, b, expected) in test_cases {
+            let actual = divergence_prefix_len(a, b);
+            assert_eq!(
+                actual, expected,
+                "we expect {a} and {b} to share {expected} leading bits"
+            );
+        }
+    }
+
+    #[test]
+    fn test_merge_ranges() {
+        let ranges = vec![
+            (Ipv4Addr::new(10, 0, 1, 0), Ipv4Addr::new(10, 0, 1, 255)),
+            (Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(10, 0, 0, 255)),
+            (Ipv4Addr::new(10, 0, 0, 128), Ipv4Addr::new(10, 0, 0, 191)),
+            (Ipv4Addr::new(10, 0, 3, 0), Ipv4Addr::new(10, 0, 3, 255)),
+            (
+                Ipv4Addr::new(255, 0, 0, 0),
+                Ipv4Addr::new(255, 255, 255, 255),
+            ),
+        ];
+
+        let expected = vec![
+            (Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(10, 0, 1, 255)),
+            (Ipv4Addr::new(10, 0, 3, 0), Ipv4Addr::new(10, 0, 3, 255)),
+            (
+                Ipv4Addr::new(255, 0, 0, 0),
+                Ipv4Addr::new(255, 255, 255, 255),
+            ),
+        ];
+
+        assert_eq!(merge_ranges(ranges), expected);
+    }
 }